@@ -0,0 +1,303 @@
+//! Introspects a D-Bus object and prints `dbus_object!` definitions for it.
+use std::collections::{BTreeSet, HashMap};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use dbus::blocking::Connection;
+use dbus::blocking::stdintf::org_freedesktop_dbus::{Introspectable, Properties};
+use dbus::channel::Channel;
+use dbus_client_codegen::introspect::{Interface, Member, Node};
+use dbus_client_codegen::signature::Type;
+use dbus_client_codegen::{camel_case, render};
+
+const USAGE: &str = "\
+Usage: dbus-client-gen [OPTIONS] <DESTINATION> [PATH]
+
+Introspects PATH (defaults to `/`) on DESTINATION and prints a `dbus_object!`
+definition for it.
+
+Options:
+  --session           Connect to the session bus (default)
+  --system            Connect to the system bus
+  --address <ADDRESS> Connect to the bus at ADDRESS
+  --peer              Connect to ADDRESS directly instead of a message bus,
+                      DESTINATION is passed on but ignored by most peers
+  --name <NAME>       Name of the object generated for PATH, defaults to the
+                      last segment of its first interface, as do the names
+                      of objects generated for child nodes
+  -r, --recursive     Also introspect child nodes, generating one
+                      `dbus_object!` per distinct set of interfaces, child
+                      nodes that cannot be introspected are skipped
+  -h, --help          Print this help";
+
+/// Interfaces implemented by (nearly) all objects and already handled by
+/// `dbus_object!`.
+const STANDARD_INTERFACES: &[&str] = &[
+    "org.freedesktop.DBus.Introspectable",
+    "org.freedesktop.DBus.Peer",
+    "org.freedesktop.DBus.Properties",
+];
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+enum Bus {
+    Session,
+    System,
+    Address(String),
+}
+
+struct Args {
+    bus: Bus,
+    peer: bool,
+    name: Option<String>,
+    recursive: bool,
+    destination: String,
+    path: String,
+}
+
+fn parse_args() -> Result<Option<Args>, String> {
+    let mut bus = Bus::Session;
+    let mut peer = false;
+    let mut name = None;
+    let mut recursive = false;
+    let mut positional = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--session" => bus = Bus::Session,
+            "--system" => bus = Bus::System,
+            "--address" => {
+                bus = Bus::Address(args.next().ok_or("`--address` requires a value")?);
+            }
+            "--peer" => peer = true,
+            "--name" => name = Some(args.next().ok_or("`--name` requires a value")?),
+            "-r" | "--recursive" => recursive = true,
+            arg if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ => positional.push(arg),
+        }
+    }
+
+    if peer && !matches!(bus, Bus::Address(_)) {
+        return Err("`--peer` requires `--address`".into());
+    }
+
+    let mut positional = positional.into_iter();
+    let destination = positional.next().ok_or("missing DESTINATION")?;
+    let path = positional.next().unwrap_or_else(|| "/".into());
+    if let Some(arg) = positional.next() {
+        return Err(format!("unexpected argument `{arg}`"));
+    }
+    Ok(Some(Args {
+        bus,
+        peer,
+        name,
+        recursive,
+        destination,
+        path,
+    }))
+}
+
+/// An object found while introspecting.
+struct Object {
+    path: String,
+    interfaces: Vec<Interface>,
+}
+
+impl Object {
+    /// The interfaces defining the type of this object.
+    fn kind(&self) -> BTreeSet<&str> {
+        self.interfaces.iter().map(|i| i.name.as_str()).collect()
+    }
+}
+
+fn introspect(
+    conn: &Connection,
+    destination: &str,
+    path: &str,
+    recursive: bool,
+    objects: &mut Vec<Object>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let xml = conn.with_proxy(destination, path, TIMEOUT).introspect()?;
+    let node = Node::from_xml(&xml)?;
+    let interfaces: Vec<_> = node
+        .interfaces
+        .into_iter()
+        .filter(|i| !STANDARD_INTERFACES.contains(&i.name.as_str()))
+        .collect();
+    if !interfaces.is_empty() {
+        objects.push(Object {
+            path: path.to_owned(),
+            interfaces,
+        });
+    }
+    if recursive {
+        for child in node.nodes.iter().filter_map(|n| n.name.as_deref()) {
+            let child = if child.starts_with('/') {
+                child.to_owned()
+            } else {
+                format!("{}/{child}", path.trim_end_matches('/'))
+            };
+            // E.g., access to some children can be denied or they vanish
+            // while introspecting.
+            if let Err(e) = introspect(conn, destination, &child, recursive, objects) {
+                eprintln!("warning: skipping `{child}`: {e}");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replaces `o` in properties with `@Name` when all values of the property
+/// refer to objects of the same generated type.
+fn resolve_objects(
+    conn: &Connection,
+    destination: &str,
+    objects: &mut [Object],
+    names: &HashMap<String, String>,
+) {
+    for object in objects {
+        let proxy = conn.with_proxy(destination, &object.path, TIMEOUT);
+        for interface in &mut object.interfaces {
+            let Ok(values) = proxy.get_all(&interface.name) else {
+                continue;
+            };
+            for member in &mut interface.members {
+                let Member::Property(property) = member else {
+                    continue;
+                };
+                let Some(value) = values.get(&property.name) else {
+                    continue;
+                };
+                let paths: Vec<_> = match &property.ty {
+                    Type::Basic('o') => value.0.as_str().into_iter().collect(),
+                    Type::Array(ty) if **ty == Type::Basic('o') => value
+                        .0
+                        .as_iter()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|v| v.as_str())
+                        .collect(),
+                    _ => continue,
+                };
+                let mut kinds = paths.iter().map(|p| names.get(*p));
+                let Some(Some(name)) = kinds.next() else {
+                    continue;
+                };
+                if kinds.all(|k| k == Some(name)) {
+                    let object = Type::Object(name.clone());
+                    property.ty = match property.ty {
+                        Type::Array(_) => Type::Array(Box::new(object)),
+                        _ => object,
+                    };
+                }
+            }
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let (conn, bus) = match &args.bus {
+        Bus::Session => (Connection::new_session()?, Some("session")),
+        Bus::System => (Connection::new_system()?, Some("system")),
+        Bus::Address(address) => {
+            let mut channel = Channel::open_private(address)?;
+            // Peers do not implement `org.freedesktop.DBus.Hello`.
+            if !args.peer {
+                channel.register()?;
+            }
+            (Connection::from(channel), None)
+        }
+    };
+
+    let mut objects = Vec::new();
+    introspect(
+        &conn,
+        &args.destination,
+        &args.path,
+        args.recursive,
+        &mut objects,
+    )?;
+    if args.name.is_some() && !objects.iter().any(|o| o.path == args.path) {
+        eprintln!(
+            "warning: ignoring `--name`, `{}` implements no interfaces",
+            args.path
+        );
+    }
+
+    // One name per distinct set of interfaces.
+    let mut kinds: Vec<(BTreeSet<&str>, String)> = Vec::new();
+    let mut paths = HashMap::new();
+    for object in &objects {
+        let kind = object.kind();
+        let name = if let Some((_, name)) = kinds.iter().find(|(k, _)| *k == kind) {
+            name.clone()
+        } else {
+            let mut name = match &args.name {
+                Some(name) if object.path == args.path => name.clone(),
+                _ => camel_case(
+                    object.interfaces[0]
+                        .name
+                        .rsplit('.')
+                        .next()
+                        .unwrap_or_default(),
+                ),
+            };
+            let base = name.clone();
+            let mut idx = 1;
+            while kinds.iter().any(|(_, n)| *n == name) {
+                idx += 1;
+                name = format!("{base}{idx}");
+            }
+            kinds.push((kind, name.clone()));
+            name
+        };
+        paths.insert(object.path.clone(), name);
+    }
+    let kinds: Vec<_> = kinds.into_iter().map(|(_, name)| name).collect();
+
+    resolve_objects(&conn, &args.destination, &mut objects, &paths);
+
+    let mut first = true;
+    for name in kinds {
+        let object = objects
+            .iter()
+            .find(|o| paths[&o.path] == name)
+            .expect("every kind has an object");
+        let mut header = format!("{:?}", args.destination);
+        if object.path == args.path {
+            header.push_str(&format!(", {:?}", object.path));
+            if let Some(bus) = bus {
+                header.push_str(", ");
+                header.push_str(bus);
+            }
+        }
+        if !first {
+            println!();
+        }
+        first = false;
+        print!("{}", render(&name, Some(&header), &object.interfaces));
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match parse_args() {
+        Ok(Some(args)) => match run(args) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("error: {e}");
+                ExitCode::FAILURE
+            }
+        },
+        Ok(None) => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
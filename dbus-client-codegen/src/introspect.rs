@@ -0,0 +1,310 @@
+//! Model of [D-Bus introspection data](https://dbus.freedesktop.org/doc/dbus-specification.html#introspection-format).
+
+use std::fmt::Display;
+
+use roxmltree::{Document, ParsingOptions};
+
+use crate::Error;
+use crate::signature::Type;
+
+/// Annotation containing the documentation of an element.
+pub const DOC_STRING: &str = "org.gtk.GDBus.DocString";
+
+/// Annotation marking methods that don't send a reply.
+pub const NO_REPLY: &str = "org.freedesktop.DBus.Method.NoReply";
+
+/// An object, i.e., `<node>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    /// Path of the node, relative for child nodes.
+    pub name: Option<String>,
+    pub interfaces: Vec<Interface>,
+    /// Child nodes, these are often empty and only contain a `name`.
+    pub nodes: Vec<Node>,
+}
+
+/// `<interface>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interface {
+    pub name: String,
+    pub members: Vec<Member>,
+    pub annotations: Vec<Annotation>,
+}
+
+/// `<method>`, `<property>` or `<signal>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Member {
+    Method(Method),
+    Property(Property),
+    Signal(Signal),
+}
+
+/// `<method>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Method {
+    pub name: String,
+    /// Arguments with `direction="in"`.
+    pub args: Vec<Arg>,
+    /// Arguments with `direction="out"`.
+    pub outputs: Vec<Arg>,
+    pub annotations: Vec<Annotation>,
+}
+
+impl Method {
+    /// Whether the method is annotated with [`NO_REPLY`] and has no outputs.
+    #[must_use]
+    pub fn no_reply(&self) -> bool {
+        self.outputs.is_empty() && annotation(&self.annotations, NO_REPLY) == Some("true")
+    }
+}
+
+/// `<property>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property {
+    pub name: String,
+    pub ty: Type,
+    pub access: Access,
+    pub annotations: Vec<Annotation>,
+}
+
+/// `<signal>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signal {
+    pub name: String,
+    pub args: Vec<Arg>,
+    pub annotations: Vec<Annotation>,
+}
+
+/// `<arg>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arg {
+    pub name: Option<String>,
+    pub ty: Type,
+    pub annotations: Vec<Annotation>,
+}
+
+/// `<annotation>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    pub name: String,
+    pub value: String,
+}
+
+/// Access of a [`Property`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl Access {
+    /// Whether the property can be read.
+    #[must_use]
+    pub fn readable(self) -> bool {
+        matches!(self, Access::Read | Access::ReadWrite)
+    }
+
+    /// Whether the property can be written.
+    #[must_use]
+    pub fn writable(self) -> bool {
+        matches!(self, Access::Write | Access::ReadWrite)
+    }
+
+    /// The value of the `access` attribute, i.e., `read`, `write` or
+    /// `readwrite`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Access::Read => "read",
+            Access::Write => "write",
+            Access::ReadWrite => "readwrite",
+        }
+    }
+}
+
+/// Returns the value of the annotation `name`.
+#[must_use]
+pub fn annotation<'a>(annotations: &'a [Annotation], name: &str) -> Option<&'a str> {
+    annotations
+        .iter()
+        .find(|a| a.name == name)
+        .map(|a| a.value.as_str())
+}
+
+/// Returns the [`DOC_STRING`] annotation.
+#[must_use]
+pub fn doc(annotations: &[Annotation]) -> Option<&str> {
+    annotation(annotations, DOC_STRING)
+}
+
+/// Returns the documentation lines of a member, the [`DOC_STRING`]s of `args`
+/// are appended as `# Arguments` list.
+#[must_use]
+pub fn doc_lines(annotations: &[Annotation], args: &[Arg]) -> Vec<String> {
+    let mut lines: Vec<_> = doc(annotations)
+        .into_iter()
+        .flat_map(|doc| doc.trim().lines().map(|line| line.trim().to_owned()))
+        .collect();
+    let args: Vec<_> = args
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, arg)| {
+            let doc = doc(&arg.annotations)?;
+            Some(format!("- `{}` {}", arg.rust_name(idx), doc.trim()))
+        })
+        .collect();
+    if !args.is_empty() {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push("# Arguments".to_owned());
+        lines.extend(args);
+    }
+    lines
+}
+
+impl Node {
+    /// Parses introspection XML.
+    ///
+    /// # Errors
+    /// Fails on invalid XML, unknown elements are ignored.
+    pub fn from_xml(xml: &str) -> Result<Self, Error> {
+        let document = Document::parse_with_options(xml, ParsingOptions {
+            allow_dtd: true,
+            ..Default::default()
+        })?;
+        Self::from_node(document.root_element())
+    }
+
+    fn from_node(node: roxmltree::Node) -> Result<Self, Error> {
+        if !node.has_tag_name("node") {
+            return Err(invalid(node, "expected `<node>`"));
+        }
+        Ok(Self {
+            name: node.attribute("name").map(ToOwned::to_owned),
+            interfaces: children(node, "interface")
+                .map(Interface::from_node)
+                .collect::<Result<_, _>>()?,
+            nodes: children(node, "node")
+                .map(Self::from_node)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Interface {
+    fn from_node(node: roxmltree::Node) -> Result<Self, Error> {
+        let mut members = Vec::new();
+        for member in node.children().filter(|n| {
+            n.has_tag_name("method") || n.has_tag_name("property") || n.has_tag_name("signal")
+        }) {
+            let name = attribute(member, "name")?.to_owned();
+            let annotations = annotations(member);
+            members.push(match member.tag_name().name() {
+                "method" => {
+                    let mut args = Vec::new();
+                    let mut outputs = Vec::new();
+                    for arg in children(member, "arg") {
+                        if arg.attribute("direction") == Some("out") {
+                            outputs.push(Arg::from_node(arg)?);
+                        } else {
+                            args.push(Arg::from_node(arg)?);
+                        }
+                    }
+                    Member::Method(Method {
+                        name,
+                        args,
+                        outputs,
+                        annotations,
+                    })
+                }
+                "property" => Member::Property(Property {
+                    name,
+                    ty: ty(member)?,
+                    access: match attribute(member, "access")? {
+                        "read" => Access::Read,
+                        "write" => Access::Write,
+                        "readwrite" => Access::ReadWrite,
+                        access => {
+                            return Err(invalid(member, format!("unknown access `{access}`")));
+                        }
+                    },
+                    annotations,
+                }),
+                "signal" => Member::Signal(Signal {
+                    name,
+                    args: children(member, "arg")
+                        .map(Arg::from_node)
+                        .collect::<Result<_, _>>()?,
+                    annotations,
+                }),
+                _ => unreachable!("filtered above"),
+            });
+        }
+        Ok(Self {
+            name: attribute(node, "name")?.to_owned(),
+            members,
+            annotations: annotations(node),
+        })
+    }
+}
+
+impl Arg {
+    /// The name of the argument as Rust identifier, `arg{idx}` if it has no
+    /// name.
+    #[must_use]
+    pub fn rust_name(&self, idx: usize) -> String {
+        self.name
+            .as_deref()
+            .map_or_else(|| format!("arg{idx}"), crate::rust_ident)
+    }
+
+    fn from_node(node: roxmltree::Node) -> Result<Self, Error> {
+        Ok(Self {
+            name: node
+                .attribute("name")
+                .filter(|name| !name.is_empty())
+                .map(ToOwned::to_owned),
+            ty: ty(node)?,
+            annotations: annotations(node),
+        })
+    }
+}
+
+fn children<'a, 'input: 'a>(
+    node: roxmltree::Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    node.children().filter(move |n| n.has_tag_name(tag))
+}
+
+fn annotations(node: roxmltree::Node) -> Vec<Annotation> {
+    children(node, "annotation")
+        .filter_map(|a| {
+            Some(Annotation {
+                name: a.attribute("name")?.to_owned(),
+                value: a.attribute("value")?.to_owned(),
+            })
+        })
+        .collect()
+}
+
+fn attribute<'a>(node: roxmltree::Node<'a, '_>, name: &str) -> Result<&'a str, Error> {
+    node.attribute(name)
+        .ok_or_else(|| invalid(node, format!("missing the `{name}` attribute")))
+}
+
+fn ty(node: roxmltree::Node) -> Result<Type, Error> {
+    let signature = attribute(node, "type")?;
+    Type::parse(signature)
+        .map_err(|e| invalid(node, format!("invalid signature `{signature}`: {e}")))
+}
+
+fn invalid(node: roxmltree::Node, message: impl Display) -> Error {
+    let name = node
+        .attribute("name")
+        .map(|name| format!(" name={name:?}"))
+        .unwrap_or_default();
+    Error::Invalid(format!("`<{}{name}>`: {message}", node.tag_name().name()))
+}
@@ -0,0 +1,441 @@
+//! Generates [`dbus_object!`](https://docs.rs/dbus-client/latest/dbus_client/macro.dbus_object.html)
+//! definitions from D-Bus introspection XML.
+//!
+//! Intended to be used from a build script, making the generated bindings
+//! readable and reviewable, in contrast to `dbus_object_from_xml!`:
+//!
+//! ```no_run
+//! // build.rs
+//! dbus_client_codegen::generate("interfaces", "src/bindings.rs").unwrap();
+//! ```
+//!
+//! Each `*.xml` file in the directory results in one `dbus_object!` named
+//! after the last segment of its file name, i.e.,
+//! `fi.w1.wpa_supplicant1.Interface.xml` results in `Interface`.
+#![warn(clippy::pedantic, clippy::cargo)]
+#![allow(clippy::module_name_repetitions)]
+
+use std::fmt::{self, Display, Write};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use introspect::{Access, Arg, Interface, Member, Node, doc_lines};
+use signature::Type;
+
+pub mod introspect;
+pub mod signature;
+
+/// Errors while generating code.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Reading or writing `path` failed.
+    Io { path: PathBuf, source: io::Error },
+    /// The XML could not be parsed.
+    Xml(roxmltree::Error),
+    /// The XML is not valid introspection data.
+    Invalid(String),
+    /// An error in the file at `path`.
+    File { path: PathBuf, source: Box<Error> },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "`{}`: {source}", path.display()),
+            Error::Xml(e) => write!(f, "invalid XML: {e}"),
+            Error::Invalid(e) => write!(f, "invalid introspection data: {e}"),
+            Error::File { path, source } => write!(f, "`{}`: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Xml(e) => Some(e),
+            Error::Invalid(_) => None,
+            Error::File { source, .. } => Some(source),
+        }
+    }
+}
+
+impl From<roxmltree::Error> for Error {
+    fn from(value: roxmltree::Error) -> Self {
+        Self::Xml(value)
+    }
+}
+
+/// Generates a `dbus_object!` for every `*.xml` file in `xml_dir` and writes
+/// them to `out_file`.
+///
+/// When called from a build script, cargo is instructed to rerun it when
+/// `xml_dir` changes.
+///
+/// # Errors
+/// Fails if reading or parsing any of the files, or writing `out_file` fails.
+pub fn generate(xml_dir: impl AsRef<Path>, out_file: impl AsRef<Path>) -> Result<(), Error> {
+    let xml_dir = xml_dir.as_ref();
+    let io = |path: &Path| {
+        let path = path.to_owned();
+        |source| Error::Io { path, source }
+    };
+
+    let mut files = fs::read_dir(xml_dir)
+        .map_err(io(xml_dir))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io(xml_dir))?;
+    files.retain(|f| f.extension().is_some_and(|e| e == "xml"));
+    files.sort();
+
+    let mut source = format!(
+        "// @generated by dbus-client-codegen from `{}`, do not edit.\n\nuse \
+         dbus_client::dbus_object;\n",
+        xml_dir.display()
+    );
+
+    for file in files {
+        let xml = fs::read_to_string(&file).map_err(io(&file))?;
+        let node = Node::from_xml(&xml).map_err(|e| Error::File {
+            path: file.clone(),
+            source: Box::new(e),
+        })?;
+        let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+        let name = camel_case(stem.rsplit('.').next().unwrap_or_default());
+        let header = node
+            .name
+            .as_deref()
+            .filter(|path| path.starts_with('/'))
+            .map(|path| format!("{path:?}"));
+        source.push('\n');
+        source.push_str(&render(&name, header.as_deref(), &node.interfaces));
+    }
+
+    if std::env::var_os("CARGO").is_some() && std::env::var_os("OUT_DIR").is_some() {
+        println!("cargo:rerun-if-changed={}", xml_dir.display());
+    }
+
+    let out_file = out_file.as_ref();
+    // Avoid touching the file, when nothing changed.
+    if fs::read_to_string(out_file).is_ok_and(|existing| existing == source) {
+        return Ok(());
+    }
+    fs::write(out_file, source).map_err(io(out_file))
+}
+
+/// Renders a `dbus_object!` named `name` implementing `interfaces`.
+///
+/// `header` is placed in the parenthesis after the name, e.g.,
+/// `"org.example", "/org/example", session`.
+///
+/// Struct types are replaced by tuple structs deriving `Append`, `Arg` and
+/// `Get`, rendered after the `dbus_object!` and named after the object and the
+/// argument or property, i.e., the property `Type: (su)` of `Notifications`
+/// results in `NotificationsType(String, u32)`. Fields use `dbus_type!`, so
+/// they are mapped to the same Rust types as in `dbus_object!`.
+#[must_use]
+pub fn render(name: &str, header: Option<&str>, interfaces: &[Interface]) -> String {
+    let mut structs = Structs {
+        object: name,
+        names: Vec::new(),
+        out: String::new(),
+    };
+    let mut out = String::new();
+    out.push_str("dbus_object! {\n");
+    let header = header.map(|h| format!("({h})")).unwrap_or_default();
+    writeln!(out, "    {name}{header}").unwrap();
+    for interface in interfaces {
+        render_interface(&mut out, &mut structs, interface);
+    }
+    out.push_str("}\n");
+    out.push_str(&structs.out);
+    out
+}
+
+/// Struct definitions replacing [`Type::Struct`]s of an object.
+struct Structs<'a> {
+    object: &'a str,
+    names: Vec<String>,
+    out: String,
+}
+
+impl Structs<'_> {
+    /// Replaces all structs in `ty` with [`Type::Named`], named `name`, or
+    /// `fallback` if `name` is already taken.
+    fn replace(&mut self, ty: &Type, name: &str, fallback: &str) -> Type {
+        match ty {
+            Type::Struct(types) => {
+                let name = self.name(name, fallback);
+                let fields = types
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, ty)| {
+                        let field = format!("{name} field{idx}");
+                        let ty = self.replace(ty, &field, &field);
+                        format!("pub dbus_client::dbus_type!({ty})")
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(
+                    self.out,
+                    "\n#[derive(Debug, dbus_client::Append, dbus_client::Arg, \
+                     dbus_client::Get)]\npub struct {name}({fields});\n"
+                )
+                .unwrap();
+                Type::Named(name, Box::new(ty.clone()))
+            }
+            Type::Array(ty) => Type::Array(Box::new(self.replace(ty, name, fallback))),
+            Type::Dict(key, value) => {
+                Type::Dict(*key, Box::new(self.replace(value, name, fallback)))
+            }
+            ty => ty.clone(),
+        }
+    }
+
+    /// A unique name for a struct, numbered if both `name` and `fallback` are
+    /// already taken.
+    fn name(&mut self, name: &str, fallback: &str) -> String {
+        let mut unique = camel_case(name);
+        if self.names.contains(&unique) {
+            unique = camel_case(fallback);
+        }
+        let base = unique.clone();
+        let mut idx = 1;
+        while self.names.contains(&unique) {
+            idx += 1;
+            unique = format!("{base}{idx}");
+        }
+        self.names.push(unique.clone());
+        unique
+    }
+
+    fn args(&mut self, member: &str, args: &[Arg]) -> Vec<(String, Type)> {
+        args.iter()
+            .enumerate()
+            .map(|(idx, arg)| {
+                let name = arg.rust_name(idx);
+                let raw = name.trim_start_matches("r#");
+                let ty = self.replace(
+                    &arg.ty,
+                    &format!("{} {raw}", self.object),
+                    &format!("{} {member} {raw}", self.object),
+                );
+                (name, ty)
+            })
+            .collect()
+    }
+}
+
+fn render_interface(out: &mut String, structs: &mut Structs, interface: &Interface) {
+    writeln!(out, "    {:?} {{", interface.name).unwrap();
+    for member in &interface.members {
+        match member {
+            Member::Method(method) => {
+                render_docs(out, doc_lines(&method.annotations, &method.args));
+                if method.no_reply() {
+                    out.push_str("        #[dbus(no_reply)]\n");
+                }
+                let args = structs
+                    .args(&method.name, &method.args)
+                    .into_iter()
+                    .map(|(name, ty)| format!("{name}: {ty}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(out, "        {}({args})", rust_ident(&method.name)).unwrap();
+                match structs.args(&method.name, &method.outputs).as_slice() {
+                    [] => {}
+                    [(_, output)] => write!(out, " -> {output}").unwrap(),
+                    outputs => {
+                        let outputs = outputs
+                            .iter()
+                            .map(|(_, ty)| ty.to_string())
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        write!(out, " -> ({outputs})").unwrap();
+                    }
+                }
+                out.push_str(";\n");
+            }
+            Member::Property(property) => {
+                render_docs(out, doc_lines(&property.annotations, &[]));
+                let access = match property.access {
+                    Access::Read => "",
+                    Access::Write => "write ",
+                    Access::ReadWrite => "mut ",
+                };
+                let name = format!("{} {}", structs.object, property.name);
+                let ty = structs.replace(&property.ty, &name, &name);
+                writeln!(out, "        {access}{}: {ty};", rust_ident(&property.name)).unwrap();
+            }
+            Member::Signal(signal) => {
+                render_docs(out, doc_lines(&signal.annotations, &signal.args));
+                let args = structs
+                    .args(&signal.name, &signal.args)
+                    .into_iter()
+                    .map(|(name, ty)| format!("{name}: {ty}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                writeln!(out, "        ~{}({args});", rust_ident(&signal.name)).unwrap();
+            }
+        }
+    }
+    out.push_str("    }\n");
+}
+
+fn render_docs(out: &mut String, lines: Vec<String>) {
+    for line in lines {
+        if line.is_empty() {
+            out.push_str("        ///\n");
+        } else {
+            writeln!(out, "        /// {line}").unwrap();
+        }
+    }
+}
+
+/// Converts a D-Bus name into a valid Rust identifier, escaping keywords.
+#[must_use]
+pub fn rust_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    let mut name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    if matches!(name.as_str(), "self" | "Self" | "super" | "crate" | "_") {
+        name.push('_');
+    } else if KEYWORDS.contains(&name.as_str()) {
+        name.insert_str(0, "r#");
+    }
+    name
+}
+
+/// Converts `snake_case` or `dotted.names` to `CamelCase`.
+#[must_use]
+pub fn camel_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .flat_map(|s| {
+            let mut chars = s.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
+
+/// Converts `CamelCase` to `snake_case`, keeping acronyms together, i.e.,
+/// `GetDBusID` results in `get_d_bus_id`.
+#[must_use]
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (idx, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev = idx.checked_sub(1).map(|idx| chars[idx]);
+            let next = chars.get(idx + 1);
+            if prev.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+                || prev.is_some_and(|p| p.is_ascii_uppercase())
+                    && next.is_some_and(char::is_ascii_lowercase)
+            {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[test]
+fn case_conversion() {
+    assert_eq!(snake_case("CreateInterface"), "create_interface");
+    assert_eq!(snake_case("WFDIEs"), "wfdi_es");
+    assert_eq!(snake_case("GetDBusID"), "get_d_bus_id");
+    assert_eq!(snake_case("Ifname2Mac"), "ifname2_mac");
+    assert_eq!(snake_case("already_snake"), "already_snake");
+    assert_eq!(camel_case("wpa_supplicant1"), "WpaSupplicant1");
+}
+
+#[test]
+fn render_notifications() {
+    let node = Node::from_xml(
+        r#"<node name="/org/freedesktop/Notifications">
+          <interface name="org.freedesktop.Notifications">
+            <method name="Notify">
+              <annotation name="org.gtk.GDBus.DocString" value="Sends a notification."/>
+              <arg name="app_name" type="s" direction="in"/>
+              <arg name="hints" type="a{sv}" direction="in">
+                <annotation name="org.gtk.GDBus.DocString" value="Extra hints."/>
+              </arg>
+              <arg name="id" type="u" direction="out"/>
+            </method>
+            <method name="CloseNotification">
+              <annotation name="org.freedesktop.DBus.Method.NoReply" value="true"/>
+              <arg name="id" type="u" direction="in"/>
+            </method>
+            <method name="GetServerInformation">
+              <arg name="name" type="s" direction="out"/>
+              <arg name="vendor" type="s" direction="out"/>
+            </method>
+            <method name="GetHistory">
+              <arg name="entries" type="a(u(ss))" direction="out"/>
+            </method>
+            <property name="Type" type="(su)" access="readwrite"/>
+            <property name="Password" type="s" access="write"/>
+            <signal name="ActionInvoked">
+              <arg type="u"/>
+              <arg name="type" type="s"/>
+            </signal>
+          </interface>
+        </node>"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        render(
+            "Notifications",
+            Some("\"/org/freedesktop/Notifications\""),
+            &node.interfaces
+        ),
+        r#"dbus_object! {
+    Notifications("/org/freedesktop/Notifications")
+    "org.freedesktop.Notifications" {
+        /// Sends a notification.
+        ///
+        /// # Arguments
+        /// - `hints` Extra hints.
+        Notify(app_name: s, hints: a{s v}) -> u;
+        #[dbus(no_reply)]
+        CloseNotification(id: u);
+        GetServerInformation() -> (s s);
+        GetHistory() -> a NotificationsEntries;
+        mut Type: NotificationsType;
+        write Password: s;
+        ~ActionInvoked(arg0: u, r#type: s);
+    }
+}
+
+#[derive(Debug, dbus_client::Append, dbus_client::Arg, dbus_client::Get)]
+pub struct NotificationsEntriesField1(pub dbus_client::dbus_type!(s), pub dbus_client::dbus_type!(s));
+
+#[derive(Debug, dbus_client::Append, dbus_client::Arg, dbus_client::Get)]
+pub struct NotificationsEntries(pub dbus_client::dbus_type!(u), pub dbus_client::dbus_type!(NotificationsEntriesField1));
+
+#[derive(Debug, dbus_client::Append, dbus_client::Arg, dbus_client::Get)]
+pub struct NotificationsType(pub dbus_client::dbus_type!(s), pub dbus_client::dbus_type!(u));
+"#
+    );
+}
@@ -0,0 +1,161 @@
+//! Parsing of D-Bus signatures.
+
+use std::fmt::{self, Display};
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// A single complete D-Bus type.
+///
+/// [`Display`] prints the type in the syntax used by `dbus_object!`, i.e., with
+/// the single types separated by spaces: `a{sv}` is printed as `a{s v}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    /// A basic type, e.g., `s` or `u`.
+    Basic(char),
+    /// `v`
+    Variant,
+    /// `a` *t*
+    Array(Box<Type>),
+    /// `a{` *k* *v* `}`
+    Dict(char, Box<Type>),
+    /// `(` *t1* *t2* ... `)`
+    Struct(Vec<Type>),
+    /// `@`*Name*, an object path (`o`) referring to an object of the type
+    /// *Name*.
+    ///
+    /// Never produced when parsing, but can be used to replace `o` in
+    /// generated code.
+    Object(String),
+    /// A Rust type named *Name* representing the contained [`Type::Struct`].
+    ///
+    /// Never produced when parsing, but can be used to replace structs in
+    /// generated code.
+    Named(String, Box<Type>),
+}
+
+/// Codes of the basic types supported by `dbus_object!`.
+pub const BASIC_TYPES: &str = "ybnqiuxtdhsog";
+
+impl Type {
+    /// Parses a D-Bus signature, e.g., `sa{sv}`, into its complete types.
+    ///
+    /// # Errors
+    /// Fails if `signature` is not a valid D-Bus signature.
+    pub fn parse_all(signature: &str) -> Result<Vec<Type>, String> {
+        let mut chars = signature.chars().peekable();
+        let mut types = Vec::new();
+        while chars.peek().is_some() {
+            types.push(Self::parse_chars(&mut chars)?);
+        }
+        Ok(types)
+    }
+
+    /// Parses a D-Bus signature consisting of exactly one complete type.
+    ///
+    /// # Errors
+    /// Fails if `signature` is not a valid D-Bus signature or contains more
+    /// or less than one complete type.
+    pub fn parse(signature: &str) -> Result<Type, String> {
+        let mut types = Self::parse_all(signature)?;
+        if types.len() == 1 {
+            Ok(types.remove(0))
+        } else {
+            Err(format!("`{signature}` must be a single complete type"))
+        }
+    }
+
+    /// The D-Bus signature of this type, i.e., `a{sv}` instead of `a{s v}`.
+    ///
+    /// [`Type::Object`]s are treated as object paths (`o`).
+    #[must_use]
+    pub fn signature(&self) -> String {
+        match self {
+            Type::Basic(code) => code.to_string(),
+            Type::Object(_) => "o".into(),
+            Type::Named(_, ty) => ty.signature(),
+            Type::Variant => "v".into(),
+            Type::Array(ty) => format!("a{}", ty.signature()),
+            Type::Dict(key, value) => format!("a{{{key}{}}}", value.signature()),
+            Type::Struct(types) => {
+                format!(
+                    "({})",
+                    types.iter().map(Type::signature).collect::<String>()
+                )
+            }
+        }
+    }
+
+    fn parse_chars(chars: &mut Peekable<Chars>) -> Result<Type, String> {
+        let Some(code) = chars.next() else {
+            return Err("unexpected end of signature".into());
+        };
+        Ok(match code {
+            'v' => Type::Variant,
+            'a' if chars.next_if_eq(&'{').is_some() => {
+                let key = chars
+                    .next()
+                    .filter(|c| BASIC_TYPES.contains(*c))
+                    .ok_or("dict entry keys must be basic types")?;
+                let value = Self::parse_chars(chars)?;
+                if chars.next() != Some('}') {
+                    return Err("dict entries must contain exactly two types".into());
+                }
+                Type::Dict(key, Box::new(value))
+            }
+            'a' => Type::Array(Box::new(Self::parse_chars(chars)?)),
+            '(' => {
+                let mut types = Vec::new();
+                while chars.next_if_eq(&')').is_none() {
+                    types.push(Self::parse_chars(chars)?);
+                }
+                if types.is_empty() {
+                    return Err("structs must contain at least one type".into());
+                }
+                Type::Struct(types)
+            }
+            code if BASIC_TYPES.contains(code) => Type::Basic(code),
+            code => return Err(format!("`{code}` is not a supported type code")),
+        })
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Basic(code) => write!(f, "{code}"),
+            Type::Object(name) => write!(f, "@{name}"),
+            Type::Named(name, _) => write!(f, "{name}"),
+            Type::Variant => write!(f, "v"),
+            Type::Array(ty) => write!(f, "a {ty}"),
+            Type::Dict(key, value) => write!(f, "a{{{key} {value}}}"),
+            Type::Struct(types) => {
+                write!(f, "(")?;
+                for (idx, ty) in types.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{ty}")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[test]
+fn parse() {
+    let types = Type::parse_all("sa{sv}a(ua{oas})").unwrap();
+    let types: Vec<_> = types.iter().map(ToString::to_string).collect();
+    assert_eq!(types, ["s", "a{s v}", "a (u a{o a s})"]);
+    let signature: String = Type::parse_all("sa{sv}a(ua{oas})")
+        .unwrap()
+        .iter()
+        .map(Type::signature)
+        .collect();
+    assert_eq!(signature, "sa{sv}a(ua{oas})");
+
+    assert!(Type::parse("a{vs}").is_err());
+    assert!(Type::parse("(ss").is_err());
+    assert!(Type::parse("()").is_err());
+    assert!(Type::parse("ss").is_err());
+}
@@ -1,7 +1,18 @@
+use std::collections::{HashMap, HashSet};
+
+use attribute_derive::FromAttr;
+use dbus_client_codegen::introspect::Access;
+use dbus_client_codegen::{camel_case, rust_ident, signature, snake_case};
+use proc_macro2::Span;
 use syn::Attribute;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
 
 use super::*;
 
+mod xml;
+pub use xml::dbus_object_from_xml;
+
 pub fn dbus_object(
     Object {
         attributes,
@@ -10,48 +21,341 @@ pub fn dbus_object(
         path,
         session,
         system,
+        asynchronous,
+        timeout,
+        naming,
+        server,
+        mock,
         interfaces,
     }: Object,
 ) -> Result {
+    if let Some(asynchronous) = &asynchronous {
+        if session || system {
+            bail!(
+                asynchronous,
+                "`session` and `system` are not supported for async objects";
+                help = "connect using `dbus-tokio`, i.e., `dbus_tokio::connection::new_session_sync()`"
+            );
+        }
+        if let Some(InterfaceImpl::Named(name)) = interfaces
+            .iter()
+            .find(|i| matches!(i, InterfaceImpl::Named(_)))
+        {
+            bail!(
+                name,
+                "`dbus_interface!`s are not supported for async objects"
+            );
+        }
+        if mock {
+            bail!(asynchronous, "`mock` is not supported for async objects");
+        }
+    }
+    if server {
+        if let Some(InterfaceImpl::Named(name)) = interfaces
+            .iter()
+            .find(|i| matches!(i, InterfaceImpl::Named(_)))
+        {
+            bail!(
+                name,
+                "`dbus_interface!`s are not supported in server traits"
+            );
+        }
+    }
+    let asynchronous = asynchronous.is_some();
     let path = path.as_slice();
     let dest = dest.as_slice();
+    let timeout = timeout
+        .map(|timeout| {
+            let timeout = timeout_millis(&timeout)?;
+            Ok::<_, syn::Error>(quote! {
+                const DEFAULT_TIMEOUT: ::std::time::Duration =
+                    ::std::time::Duration::from_millis(#timeout);
+            })
+        })
+        .transpose()?;
+
+    // Unqualified members of all interfaces end up in the same `impl`.
+    let mut functions_by_name = HashMap::new();
+    for interface in interfaces.iter().filter_map(|i| match i {
+        InterfaceImpl::Anonymous(interface) => Some(interface),
+        InterfaceImpl::Named(_) => None,
+    }) {
+        let mut own = HashSet::new();
+        for function in interface.members.iter().flat_map(|m| m.functions(naming)) {
+            if !own.insert(function.to_string()) {
+                bail!(
+                    function,
+                    "`{function}` is defined multiple times in `{}`",
+                    interface.name.value();
+                    help = "rename one of them and specify its D-Bus name using \
+                            `#[dbus(name = \"...\")]`"
+                );
+            }
+            if interface.alias.is_some() {
+                continue;
+            }
+            if let Some(other) = functions_by_name.insert(function.to_string(), &interface.name) {
+                bail!(
+                    function,
+                    "`{function}` is defined by both `{}` and `{}`",
+                    other.value(),
+                    interface.name.value();
+                    help = "add an alias, i.e., `{:?} as alias {{ ... }}`, to only access its \
+                            members through `alias()`",
+                    interface.name.value()
+                );
+            }
+        }
+    }
+
+    // Functions of `dbus_interface!`s are only known after their expansion.
+    let mut functions_by_name: Vec<_> = functions_by_name.into_iter().collect();
+    functions_by_name.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut conflicts = TokenStream::new();
+    let named: Vec<_> = interfaces
+        .iter()
+        .filter_map(|i| match i {
+            InterfaceImpl::Named(path) => Some(path),
+            InterfaceImpl::Anonymous(_) => None,
+        })
+        .collect();
+    for (idx, path) in named.iter().enumerate() {
+        let trait_name = path.to_token_stream().to_string().replace(' ', "");
+        let functions = quote_spanned!(path.span()=>
+            <#name<'static> as #path<'static>>::__FUNCTIONS
+        );
+        for (function, interface) in &functions_by_name {
+            let message = format!(
+                "`{function}` is defined by both `{}` and `{trait_name}`, add an alias, i.e., \
+                 `{:?} as alias {{ ... }}`, to only access its members through `alias()`",
+                interface.value(),
+                interface.value(),
+            );
+            quote_spanned! {path.span()=>
+                const _: () = if ::dbus_client::__private::contains(#functions, #function) {
+                    panic!("{}", #message);
+                };
+            }
+            .to_tokens(&mut conflicts);
+        }
+        for other in &named[..idx] {
+            let other_name = other.to_token_stream().to_string().replace(' ', "");
+            let message = format!(
+                "`{other_name}` and `{trait_name}` define functions with the same name, call them \
+                 using `{trait_name}::function(&object)`"
+            );
+            quote_spanned! {path.span()=>
+                const _: () = if ::dbus_client::__private::intersect(
+                    #functions,
+                    <#name<'static> as #other<'static>>::__FUNCTIONS,
+                ) {
+                    panic!("{}", #message);
+                };
+            }
+            .to_tokens(&mut conflicts);
+        }
+    }
 
-    let interfaces = interfaces.into_iter().map(|i| i.expand(&name));
+    let items: TokenStream = interfaces
+        .iter()
+        .map(|i| i.items(&name, naming, server, asynchronous))
+        .collect::<Result<_>>()?;
+    let server = server.then(|| server_trait(&name, &interfaces, naming, asynchronous));
+    let mock = mock.then(|| mock_trait(&name, &interfaces, naming));
+    let declarations: TokenStream = interfaces.iter().map(InterfaceImpl::declarations).collect();
+    let interfaces = interfaces
+        .into_iter()
+        .map(|i| i.expand(&name, naming, asynchronous));
 
     let mut extra_traits = TokenStream::new();
 
     if session {
         quote! {
-            impl ::dbus_client::CommonlySession for #name<'_> {}
+            impl<C> ::dbus_client::CommonlySession for #name<'_, C> {}
         }
         .to_tokens(&mut extra_traits);
     }
     if system {
         quote! {
-            impl ::dbus_client::CommonlySystem for #name<'_> {}
+            impl<C> ::dbus_client::CommonlySystem for #name<'_, C> {}
         }
         .to_tokens(&mut extra_traits);
     }
 
+    // Blocking objects are generic over their `Transport`.
+    let (generics, clone_generics, self_ty, owned_ty, owned_connection) = if asynchronous {
+        (
+            TokenStream::new(),
+            TokenStream::new(),
+            quote!(#name<'_>),
+            quote!(#name<'static>),
+            quote!(this.connection),
+        )
+    } else {
+        (
+            quote!(<C>),
+            quote!(<C: Clone>),
+            quote!(#name<'_, C>),
+            quote!(#name<'static, C>),
+            quote!(this.connection.into_owned()),
+        )
+    };
+
+    let (struct_generics, connection, object_impl, properties) = if asynchronous {
+        (
+            quote!(<'a>),
+            quote!(::std::sync::Arc<::dbus_client::__private::dbus::nonblock::SyncConnection>),
+            quote! {
+                # use dbus_client::__private::dbus::nonblock::SyncConnection;
+                # use dbus_client::__private::dbus::strings::{BusName, Path};
+                # use std::sync::Arc;
+                # use std::time::Duration;
+
+                impl<'a> ::dbus_client::nonblock::DbusObject<'a> for #name<'a> {
+                    #timeout
+
+                    fn new(
+                        connection: Arc<SyncConnection>,
+                        destination: impl Into<BusName<'a>>,
+                        path: impl Into<Path<'a>>,
+                        timeout: impl Into<Option<Duration>>,
+                    ) -> Self {
+                        Self {
+                            connection,
+                            destination: destination.into(),
+                            path: path.into(),
+                            timeout: timeout.into().unwrap_or(Self::DEFAULT_TIMEOUT),
+                        }
+                    }
+
+                    fn connection(this: &Self) -> &Arc<SyncConnection> {
+                        &this.connection
+                    }
+
+                    fn destination(this: &Self) -> &BusName<'a> {
+                        &this.destination
+                    }
+
+                    fn path(this: &Self) -> &Path<'a> {
+                        &this.path
+                    }
+                    fn timeout(this: &Self) -> Duration {
+                        this.timeout
+                    }
+
+                    fn declarations() -> Vec<::dbus_client::verify::Declaration> {
+                        let mut declarations = Vec::new();
+                        #declarations
+                        declarations
+                    }
+                }
+            },
+            TokenStream::new(),
+        )
+    } else {
+        (
+            quote!(<'a, C = ::dbus_client::__private::dbus::blocking::Connection>),
+            quote!(::dbus_client::MaybeOwned<'a, C>),
+            quote! {
+                # use dbus_client::__private::dbus::strings::{BusName, Path};
+                # use std::time::Duration;
+                # use dbus_client::{DbusObject, MaybeOwned, Transport};
+
+                impl<'a, C: Transport> DbusObject<'a> for #name<'a, C> {
+                    #timeout
+
+                    type Connection = C;
+
+                    fn new(
+                        connection: impl Into<MaybeOwned<'a, C>>,
+                        destination: impl Into<BusName<'a>>,
+                        path: impl Into<Path<'a>>,
+                        timeout: impl Into<Option<Duration>>,
+                    ) -> Self {
+                        Self {
+                            connection: connection.into(),
+                            destination: destination.into(),
+                            path: path.into(),
+                            timeout: timeout.into().unwrap_or(Self::DEFAULT_TIMEOUT),
+                        }
+                    }
+
+                    fn connection(this: &Self) -> &C {
+                        AsRef::as_ref(&this.connection)
+                    }
+
+                    fn destination(this: &Self) -> &BusName<'a> {
+                        &this.destination
+                    }
+
+                    fn path(this: &Self) -> &Path<'a> {
+                        &this.path
+                    }
+                    fn timeout(this: &Self) -> Duration {
+                        this.timeout
+                    }
+
+                    fn declarations() -> Vec<::dbus_client::verify::Declaration> {
+                        let mut declarations = Vec::new();
+                        #declarations
+                        declarations
+                    }
+                }
+            },
+            quote! {
+                # use dbus_client::__private::dbus::arg::{self, Get};
+                # use dbus_client::__private::dbus::blocking::stdintf::org_freedesktop_dbus::Properties;
+                # use dbus_client::{DbusObject, Transport};
+
+                impl<C: Transport> Properties for #name<'_, C> {
+
+                    fn get<R0: for<'b> Get<'b> + 'static>(&self, interface_name: &str, property_name: &str) -> Result<R0, dbus::Error> {
+                        DbusObject::method_call(self, "org.freedesktop.DBus.Properties", "Get", (interface_name, property_name, ))
+                            .map(|r: (arg::Variant<R0>, )| (r.0).0)
+                            .map_err(dbus::Error::from)
+                    }
+
+                    fn get_all(&self, interface_name: &str) -> Result<arg::PropMap, dbus::Error> {
+                        DbusObject::method_call(self, "org.freedesktop.DBus.Properties", "GetAll", (interface_name, ))
+                            .map(|r: (arg::PropMap, )| r.0)
+                            .map_err(dbus::Error::from)
+                    }
+
+                    fn set<I2: arg::Arg + arg::Append>(&self, interface_name: &str, property_name: &str, value: I2) -> Result<(), dbus::Error> {
+                        DbusObject::method_call(self, "org.freedesktop.DBus.Properties", "Set", (interface_name, property_name, arg::Variant(value), ))
+                            .map_err(dbus::Error::from)
+                    }
+                }
+            },
+        )
+    };
+
     Ok(quote! {
-        # use dbus_client::__private::dbus::blocking::Connection;
         # use dbus_client::__private::dbus::strings::{BusName, Path, Signature};
-        # use dbus_client::__private::dbus::arg::{self, Arg, ArgType, Get, Append, IterAppend};
-        # use dbus_client::__private::dbus::blocking::stdintf::org_freedesktop_dbus::Properties;
+        # use dbus_client::__private::dbus::arg::{self, Arg, ArgType, Append, IterAppend};
         # use std::time::Duration;
-        # use dbus_client::{CommonDestination, CommonPath, DbusObject, MaybeOwned};
+        # use dbus_client::{CommonDestination, CommonPath};
 
         #(#attributes)*
-        pub struct #name<'a> {
-            connection: MaybeOwned<'a, Connection>,
+        pub struct #name #struct_generics {
+            connection: #connection,
             destination: BusName<'a>,
             path: Path<'a>,
             timeout: Duration,
         }
-        #[allow(non_snake_case)]
+
+        #items
+        #server
+        #mock
+        #[allow(
+            non_snake_case,
+            clippy::too_many_arguments,
+            clippy::mutable_key_type,
+            clippy::unused_async
+        )]
         const _: () = {
 
-        impl std::fmt::Debug for #name<'_> {
+        impl #generics std::fmt::Debug for #self_ty {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
                 f.debug_struct(stringify!(#name))
                     .field("destination", &self.destination)
@@ -61,75 +365,73 @@ pub fn dbus_object(
             }
         }
 
-        impl<'a> DbusObject<'a> for #name<'a> {
-            fn new(
-                connection: impl Into<MaybeOwned<'a, Connection>>,
-                destination: impl Into<BusName<'a>>,
-                path: impl Into<Path<'a>>,
-                timeout: Duration
-            ) -> Self {
+        impl #clone_generics Clone for #self_ty {
+            fn clone(&self) -> Self {
                 Self {
-                    connection: connection.into(),
-                    destination: destination.into(),
-                    path: path.into(),
-                    timeout
+                    connection: self.connection.clone(),
+                    destination: self.destination.clone(),
+                    path: self.path.clone(),
+                    timeout: self.timeout,
                 }
             }
+        }
 
-            fn connection(this: &Self) -> &Connection {
-                AsRef::as_ref(&this.connection)
-            }
-
-            fn destination(this: &Self) -> &BusName<'a> {
-                &this.destination
-            }
-
-            fn path(this: &Self) -> &Path<'a> {
-                &this.path
-            }
-            fn timeout(this: &Self) -> Duration {
-                this.timeout
+        impl #clone_generics #self_ty {
+            /// Converts into an object not borrowing anything, sharing the
+            /// connection if it is borrowed, e.g., an `Arc<SyncConnection>`.
+            pub fn into_owned(this: Self) -> #owned_ty {
+                #name {
+                    connection: #owned_connection,
+                    destination: this.destination.into_static(),
+                    path: this.path.into_static(),
+                    timeout: this.timeout,
+                }
             }
         }
 
-        #(impl CommonDestination for #name<'_> {
+        #object_impl
+
+        #(impl #generics CommonDestination for #self_ty {
             const DESTINATION: &'static str = #dest;
         })*
 
-        #(impl CommonPath for #name<'_> {
+        #(impl #generics CommonPath for #self_ty {
             const PATH: &'static str = #path;
         })*
 
         #extra_traits
+        #conflicts
 
-        impl Properties for #name<'_> {
+        #properties
 
-            fn get<R0: for<'b> Get<'b> + 'static>(&self, interface_name: &str, property_name: &str) -> Result<R0, dbus::Error> {
-                DbusObject::method_call(self, "org.freedesktop.DBus.Properties", "Get", (interface_name, property_name, ))
-                    .and_then(|r: (arg::Variant<R0>, )| Ok((r.0).0, ))
-            }
+        impl #generics Arg for #self_ty {
+            const ARG_TYPE: ArgType = <Path as Arg>::ARG_TYPE;
 
-            fn get_all(&self, interface_name: &str) -> Result<arg::PropMap, dbus::Error> {
-                DbusObject::method_call(self, "org.freedesktop.DBus.Properties", "GetAll", (interface_name, ))
-                    .and_then(|r: (arg::PropMap, )| Ok(r.0, ))
+            fn signature() -> Signature<'static> {
+                <Path as Arg>::signature()
             }
+        }
 
-            fn set<I2: arg::Arg + arg::Append>(&self, interface_name: &str, property_name: &str, value: I2) -> Result<(), dbus::Error> {
-                DbusObject::method_call(self, "org.freedesktop.DBus.Properties", "Set", (interface_name, property_name, arg::Variant(value), ))
+        impl #generics Append for #self_ty {
+            fn append_by_ref(&self, __i: &mut IterAppend<'_>) {
+                self.path.append_by_ref(__i);
             }
         }
 
-        impl Arg for #name<'_> {
-            const ARG_TYPE: ArgType = <Path as Arg>::ARG_TYPE;
+        impl #generics arg::DictKey for #self_ty {}
 
-            fn signature() -> Signature<'static> {
-                <Path as Arg>::signature()
+        impl #generics PartialEq for #self_ty {
+            fn eq(&self, other: &Self) -> bool {
+                self.destination == other.destination && self.path == other.path
             }
         }
 
-        impl Append for #name<'_> {
-            fn append_by_ref(&self, __i: &mut IterAppend<'_>) {
-                self.path.append_by_ref(__i);
+        impl #generics Eq for #self_ty {}
+
+        impl #generics std::hash::Hash for #self_ty {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.destination.hash(state);
+                self.path.hash(state);
             }
         }
 
@@ -138,6 +440,434 @@ pub fn dbus_object(
     })
 }
 
+/// The trait `{name}Server` implemented by servers of the object `name` and
+/// its `dbus_client::server::Interfaces` implementation.
+fn server_trait(
+    name: &Ident,
+    interfaces: &[InterfaceImpl],
+    naming: Naming,
+    asynchronous: bool,
+) -> TokenStream {
+    let server = format_ident!("{name}Server");
+    let doc =
+        format!(" Server side of [`{name}`], served using [`dbus_client::server::register`].");
+    let interfaces: Vec<_> = interfaces
+        .iter()
+        .filter_map(|i| match i {
+            InterfaceImpl::Anonymous(interface) => Some(interface),
+            InterfaceImpl::Named(_) => None,
+        })
+        .collect();
+    let (functions, registrations): (Vec<_>, Vec<_>) =
+        interfaces.iter().map(|i| i.server(naming)).unzip();
+    let (generics, object) = if asynchronous {
+        (TokenStream::new(), quote!(#name<'_>))
+    } else {
+        (quote!(, C), quote!(#name<'_, C>))
+    };
+    // Declared errors can be returned by implementations using `?`.
+    let errors = interfaces
+        .iter()
+        .filter_map(|i| i.error_type(&i.view(name)))
+        .map(|error| {
+            quote! {
+                impl From<#error> for MethodErr {
+                    fn from(error: #error) -> Self {
+                        ::dbus_client::Error::from(error).into()
+                    }
+                }
+            }
+        });
+    quote! {
+        # use ::dbus_client::server::{Crossroads, IfaceBuilder, IfaceToken, Interfaces, MethodErr};
+
+        #[doc = #doc]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub trait #server: Send + 'static {
+            #(#functions)*
+        }
+
+        impl<T: #server #generics> Interfaces<T> for #object {
+            fn register(crossroads: &mut Crossroads) -> Vec<IfaceToken<T>> {
+                vec![#(#registrations),*]
+            }
+        }
+
+        #(#errors)*
+    }
+}
+
+/// The trait `{name}Api` implemented by the object `name` and its mock
+/// `Mock{name}`, only generated with the `mock` feature of `dbus-client`.
+///
+/// Contains the methods and properties of interfaces without alias, that do
+/// not involve objects.
+fn mock_trait(name: &Ident, interfaces: &[InterfaceImpl], naming: Naming) -> TokenStream {
+    struct Function<'a> {
+        attributes: &'a [Attribute],
+        name: Ident,
+        args: Vec<(Ident, TokenStream)>,
+        output: &'a Type,
+        error: TokenStream,
+    }
+
+    let mut functions = Vec::new();
+    for interface in interfaces.iter().filter_map(|i| match i {
+        InterfaceImpl::Anonymous(interface) if interface.alias.is_none() => Some(interface),
+        _ => None,
+    }) {
+        let error = interface
+            .error_type(&interface.view(name))
+            .map_or_else(|| quote!(::dbus_client::Error), ToTokens::into_token_stream);
+        for member in &interface.members {
+            match member {
+                Member::Method(Method {
+                    attributes,
+                    name,
+                    args,
+                    output,
+                    ..
+                }) if !output.contains_object() && !args.iter().any(|a| a.ty.contains_object()) => {
+                    functions.push(Function {
+                        attributes,
+                        name: naming.method(name),
+                        args: args.iter().map(|a| (a.name.clone(), a.ty.wire())).collect(),
+                        output,
+                        error: error.clone(),
+                    });
+                }
+                Member::Property(Property {
+                    attributes,
+                    access,
+                    name,
+                    ty,
+                    ..
+                }) if !ty.contains_object() => {
+                    if access.readable() {
+                        functions.push(Function {
+                            attributes,
+                            name: naming.getter(name),
+                            args: Vec::new(),
+                            output: ty,
+                            error: quote!(::dbus_client::Error),
+                        });
+                    }
+                    if access.writable() {
+                        functions.push(Function {
+                            attributes,
+                            name: naming.setter(name),
+                            args: vec![(format_ident!("value"), ty.wire())],
+                            output: &Type::Empty,
+                            error: quote!(::dbus_client::Error),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let api = format_ident!("{name}Api");
+    let mock = format_ident!("Mock{name}");
+    let signatures: Vec<_> = functions
+        .iter()
+        .map(|function| {
+            let Function {
+                name,
+                args,
+                output,
+                error,
+                ..
+            } = function;
+            let args = args.iter().map(|(name, ty)| quote!(#name: #ty));
+            quote!(fn #name(&self, #(#args),*) -> ::std::result::Result<#output, #error>)
+        })
+        .collect();
+    let attributes = functions.iter().map(|f| f.attributes);
+    let functions_names: Vec<_> = functions.iter().map(|f| &f.name).collect();
+    let arg_names: Vec<Vec<_>> = functions
+        .iter()
+        .map(|f| f.args.iter().map(|(name, _)| name).collect())
+        .collect();
+    let keys: Vec<_> = functions
+        .iter()
+        .map(|f| LitStr::new(&f.name.unraw().to_string(), f.name.span()))
+        .collect();
+    let expectations = functions.iter().zip(&keys).map(|(function, key)| {
+        let Function {
+            name: function,
+            args,
+            output,
+            error,
+            ..
+        } = function;
+        let expect = format_ident!("expect_{}", function.unraw());
+        let doc = format!(" Expects calls of [`{api}::{}`].", function.unraw());
+        let args = args.iter().map(|(_, ty)| ty);
+        let output = output.wire();
+        quote! {
+            # use ::dbus_client::mock::Expectation;
+
+            #[doc = #doc]
+            pub fn #expect(&mut self) -> &mut Expectation<(#(#args,)*), #output, #error> {
+                self.mock.expect(#key)
+            }
+        }
+    });
+    let doc = format!(
+        " Methods and properties of [`{name}`], also implemented by `{mock}` with the `mock` \
+         feature of `dbus-client`."
+    );
+    let mock_doc = format!(" Mock of [`{name}`], program it using the `expect_*` functions.");
+
+    quote! {
+        #[doc = #doc]
+        #[allow(non_snake_case, clippy::too_many_arguments, clippy::missing_errors_doc)]
+        pub trait #api {
+            #(#(#attributes)* #signatures;)*
+        }
+
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        impl<C: ::dbus_client::Transport> #api for #name<'_, C> {
+            #(#signatures {
+                #name::#functions_names(self, #(#arg_names),*)
+            })*
+        }
+
+        ::dbus_client::__mock! {
+            #[doc = #mock_doc]
+            #[derive(Default)]
+            pub struct #mock {
+                mock: ::dbus_client::mock::Mock,
+            }
+
+            #[allow(non_snake_case)]
+            impl #mock {
+                #(#expectations)*
+
+                /// Checks that all expectations were satisfied and removes them, see
+                /// [`Mock::checkpoint`](::dbus_client::mock::Mock::checkpoint).
+                pub fn checkpoint(&mut self) {
+                    self.mock.checkpoint();
+                }
+            }
+
+            #[allow(non_snake_case, clippy::too_many_arguments)]
+            impl #api for #mock {
+                #(#signatures {
+                    self.mock.call(#keys, (#(#arg_names,)*))
+                })*
+            }
+        }
+    }
+}
+
+/// The Rust type a type of `dbus_object!` is read as, e.g., `String` for `s`,
+/// used by `dbus-client-codegen` for fields of generated structs.
+pub fn dbus_type(DbusType(ty): DbusType) -> TokenStream {
+    ty.wire()
+}
+
+/// Input of [`dbus_type`].
+pub struct DbusType(Type);
+
+impl Parse for DbusType {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse().map(Self)
+    }
+}
+
+pub fn dbus_interface(
+    NamedInterface {
+        mut attributes,
+        name,
+        interface,
+    }: NamedInterface,
+) -> Result {
+    let options = ObjectOptions::remove_attributes(&mut attributes)?;
+    if options.server || options.mock {
+        bail!(
+            name,
+            "`server` and `mock` are only supported on `dbus_object!`"
+        );
+    }
+    let naming = options.naming();
+    let signals = interface.signals(&name, naming, false);
+    let error = interface.error_type(&name);
+    let errors = interface.errors(error.as_ref())?;
+    let target = Target::interface(name.clone(), naming).with_error(error);
+    let members = interface
+        .members
+        .iter()
+        .map(|m| m.expand(&target, &interface.name));
+    let declarations = interface.declarations();
+    let functions = interface
+        .members
+        .iter()
+        .flat_map(|m| m.functions(naming))
+        .map(|f| f.to_string());
+    if let Some(alias) = &interface.alias {
+        bail!(alias, "aliases are not supported in `dbus_interface!`");
+    }
+    Ok(quote! {
+        # use dbus_client::__private::dbus::blocking::stdintf::org_freedesktop_dbus::Properties;
+        # use dbus_client::DbusObject;
+
+        #(#attributes)*
+        #[allow(
+            non_snake_case,
+            unknown_lints,
+            mismatched_lifetime_syntaxes,
+            clippy::too_many_arguments,
+            clippy::mutable_key_type
+        )]
+        pub trait #name<'a>: DbusObject<'a> + Properties {
+            #(#members)*
+
+            /// Functions defined by this trait, checked for conflicts by
+            /// `dbus_object!`.
+            #[doc(hidden)]
+            const __FUNCTIONS: &'static [&'static str] = &[#(#functions),*];
+
+            #[doc(hidden)]
+            fn __declarations(declarations: &mut Vec<::dbus_client::verify::Declaration>) {
+                #declarations
+            }
+        }
+
+        #signals
+        #errors
+    })
+}
+
+/// A `dbus_interface!`, i.e., an [`Interface`] with a name.
+pub struct NamedInterface {
+    attributes: Vec<Attribute>,
+    name: Ident,
+    interface: Interface,
+}
+
+impl Parse for NamedInterface {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            attributes: Attribute::parse_outer(input)?,
+            name: input.parse()?,
+            interface: input.parse()?,
+        })
+    }
+}
+
+/// `#[dbus(...)]` on a `dbus_object!` or `dbus_interface!`.
+#[derive(FromAttr)]
+#[attribute(ident = dbus)]
+struct ObjectOptions {
+    /// Use the names of the members as is, i.e., `get_DebugLevel()` and
+    /// `CreateInterface()`.
+    legacy_naming: bool,
+    /// Generate a server trait `{Name}Server`, see `dbus_client::server`.
+    server: bool,
+    /// Generate a trait `{Name}Api` and its mock `Mock{Name}`, see
+    /// `dbus_client::mock`.
+    mock: bool,
+}
+
+impl ObjectOptions {
+    fn naming(&self) -> Naming {
+        if self.legacy_naming {
+            Naming::Legacy
+        } else {
+            Naming::Rust
+        }
+    }
+}
+
+/// How the functions generated for members are named.
+#[derive(Clone, Copy)]
+enum Naming {
+    /// `debug_level()`, `set_debug_level()`, `create_interface()` and
+    /// `on_interface_added()`.
+    Rust,
+    /// `get_DebugLevel()`, `set_DebugLevel()`, `CreateInterface()` and
+    /// `on_InterfaceAdded()`.
+    Legacy,
+}
+
+impl Naming {
+    fn getter(self, name: &Ident) -> Ident {
+        match self {
+            Naming::Rust => ident(
+                &rust_ident(&snake_case(&name.unraw().to_string())),
+                name.span(),
+            ),
+            Naming::Legacy => format_ident!("get_{name}"),
+        }
+    }
+
+    fn setter(self, name: &Ident) -> Ident {
+        match self {
+            Naming::Rust => format_ident!("set_{}", snake_case(&name.unraw().to_string())),
+            Naming::Legacy => format_ident!("set_{name}"),
+        }
+    }
+
+    fn method(self, name: &Ident) -> Ident {
+        match self {
+            Naming::Rust => ident(
+                &rust_ident(&snake_case(&name.unraw().to_string())),
+                name.span(),
+            ),
+            Naming::Legacy => name.clone(),
+        }
+    }
+
+    fn signal_handler(self, name: &Ident) -> Ident {
+        match self {
+            Naming::Rust => format_ident!("on_{}", snake_case(&name.unraw().to_string())),
+            Naming::Legacy => format_ident!("on_{name}"),
+        }
+    }
+
+    fn signal_struct(self, prefix: &Ident, name: &Ident) -> Ident {
+        match self {
+            Naming::Rust => format_ident!("{prefix}{}", camel_case(&name.unraw().to_string())),
+            Naming::Legacy => format_ident!("{prefix}{name}"),
+        }
+    }
+}
+
+/// Parses a duration, i.e., `500ms`, `5s` or `2min`, into milliseconds.
+fn timeout_millis(timeout: &LitInt) -> syn::Result<u64> {
+    let factor = match timeout.suffix() {
+        "ms" => 1,
+        "s" => 1000,
+        "min" => 60_000,
+        _ => {
+            return Err(syn::Error::new(
+                timeout.span(),
+                "expected a duration, i.e., `500ms`, `5s` or `2min`",
+            ));
+        }
+    };
+    timeout
+        .base10_parse::<u64>()?
+        .checked_mul(factor)
+        .ok_or_else(|| syn::Error::new(timeout.span(), "timeout is too large"))
+}
+
+/// Name of the variant of `function` returning objects that share the
+/// connection, e.g., `interfaces_owned`.
+fn owned_function(function: &Ident) -> Ident {
+    format_ident!("{}_owned", function.unraw())
+}
+
+/// Creates an identifier from the output of [`rust_ident`].
+fn ident(name: &str, span: Span) -> Ident {
+    match name.strip_prefix("r#") {
+        Some(name) => Ident::new_raw(name, span),
+        None => Ident::new(name, span),
+    }
+}
+
 pub struct Object {
     attributes: Vec<Attribute>,
     name: Ident,
@@ -147,17 +877,44 @@ pub struct Object {
     path: Option<LitStr>,
     session: bool,
     system: bool,
+    /// `async`, generates an object implementing
+    /// `dbus_client::nonblock::DbusObject`.
+    asynchronous: Option<Token![async]>,
+    /// `timeout = 5s`
+    timeout: Option<LitInt>,
+    naming: Naming,
+    /// `#[dbus(server)]`, generates a server trait.
+    server: bool,
+    /// `#[dbus(mock)]`, generates a trait and its mock.
+    mock: bool,
     interfaces: Vec<InterfaceImpl>,
 }
 
 impl Parse for Object {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let attributes = Attribute::parse_outer(input)?;
+        let mut object = Self::parse_header(input)?;
+
+        while !input.is_empty() {
+            object.interfaces.push(input.parse()?);
+        }
+
+        Ok(object)
+    }
+}
+
+impl Object {
+    /// Parses everything but the interfaces, i.e., attributes, name and the
+    /// optional `(...)` containing destination, path etc.
+    fn parse_header(input: ParseStream) -> syn::Result<Self> {
+        let mut attributes = Attribute::parse_outer(input)?;
+        let options = ObjectOptions::remove_attributes(&mut attributes)?;
         let name = input.parse()?;
         let mut dest = None;
         let mut path = None;
         let mut session = None;
         let mut system = None;
+        let mut asynchronous = None;
+        let mut timeout = None;
         if input.peek(Paren) {
             let content;
             parenthesized!(content in input);
@@ -210,24 +967,44 @@ impl Parse for Object {
                             system = Some(new);
                         }
                     }
+                    ExtraTraits::Async(new) => {
+                        if let Some(old) = asynchronous {
+                            let mut error = syn::Error::new_spanned(new, "async was already set");
+                            error.combine(syn::Error::new_spanned(
+                                old,
+                                "async was already set here",
+                            ));
+                            return Err(error);
+                        } else {
+                            asynchronous = Some(new);
+                        }
+                    }
+                    ExtraTraits::Timeout(new) => {
+                        if let Some(old) = timeout {
+                            let mut error = syn::Error::new_spanned(new, "timeout already defined");
+                            error.combine(syn::Error::new_spanned(old, "timeout was defined here"));
+                            return Err(error);
+                        } else {
+                            timeout = Some(new);
+                        }
+                    }
                 }
             }
         }
 
-        let mut interfaces = Vec::new();
-
-        while !input.is_empty() {
-            interfaces.push(input.parse()?);
-        }
-
         Ok(Self {
             attributes,
             name,
             dest,
             path,
-            interfaces,
+            interfaces: Vec::new(),
             session: session.is_some(),
             system: system.is_some(),
+            asynchronous,
+            timeout,
+            naming: options.naming(),
+            server: options.server,
+            mock: options.mock,
         })
     }
 }
@@ -237,6 +1014,13 @@ mod property {
 
     custom_keyword!(session);
     custom_keyword!(system);
+    custom_keyword!(timeout);
+}
+
+mod member {
+    use syn::custom_keyword;
+
+    custom_keyword!(write);
 }
 
 enum ExtraTraits {
@@ -244,6 +1028,9 @@ enum ExtraTraits {
     Path(LitStr),
     Session(property::session),
     System(property::system),
+    Async(Token![async]),
+    /// `timeout = 5s`
+    Timeout(LitInt),
 }
 
 impl Parse for ExtraTraits {
@@ -253,6 +1040,12 @@ impl Parse for ExtraTraits {
             Self::Session(input.parse()?)
         } else if la.peek(property::system) {
             Self::System(input.parse()?)
+        } else if la.peek(Token![async]) {
+            Self::Async(input.parse()?)
+        } else if la.peek(property::timeout) {
+            input.parse::<property::timeout>()?;
+            input.parse::<Token![=]>()?;
+            Self::Timeout(input.parse()?)
         } else if la.peek(LitStr) {
             let str: LitStr = input.parse()?;
             let value = str.value();
@@ -298,164 +1091,1057 @@ impl Parse for ExtraTraits {
 
 enum InterfaceImpl {
     Anonymous(Interface),
-    Named(Ident),
+    /// A `dbus_interface!`.
+    Named(Path),
 }
 
 impl Parse for InterfaceImpl {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        if input.peek(Ident) {
-            let name = input.parse()?;
+        if input.peek(LitStr) {
+            input.parse().map(Self::Anonymous)
+        } else {
+            let name = Path::parse_mod_style(input)?;
             input.parse::<Token![;]>()?;
             Ok(Self::Named(name))
+        }
+    }
+}
+
+impl InterfaceImpl {
+    /// Public items, i.e., signal structs and views.
+    fn items(
+        &self,
+        struct_name: &Ident,
+        naming: Naming,
+        server: bool,
+        asynchronous: bool,
+    ) -> Result<TokenStream> {
+        Ok(match self {
+            InterfaceImpl::Anonymous(interface) => {
+                let view = interface.view(struct_name);
+                let doc = format!(
+                    " Members of `{}`, see [`{struct_name}::{}`].",
+                    interface.name.value(),
+                    interface.accessor()
+                );
+                let signals =
+                    interface.signals(&interface.signal_prefix(struct_name), naming, server);
+                let errors = interface.errors(interface.error_type(&view).as_ref())?;
+                let view = if asynchronous {
+                    quote! {
+                        #[doc = #doc]
+                        #[derive(Debug, Clone, Copy)]
+                        pub struct #view<'a>(&'a #struct_name<'a>);
+                    }
+                } else {
+                    // Derives would require `C: Clone`.
+                    quote! {
+                        #[doc = #doc]
+                        pub struct #view<'a, C = ::dbus_client::__private::dbus::blocking::Connection>(
+                            &'a #struct_name<'a, C>,
+                        );
+
+                        impl<C> ::std::fmt::Debug for #view<'_, C> {
+                            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                                f.debug_tuple(stringify!(#view)).field(self.0).finish()
+                            }
+                        }
+
+                        impl<C> Clone for #view<'_, C> {
+                            fn clone(&self) -> Self {
+                                *self
+                            }
+                        }
+
+                        impl<C> Copy for #view<'_, C> {}
+                    }
+                };
+                quote! {
+                    #view
+                    #signals
+                    #errors
+                }
+            }
+            InterfaceImpl::Named(_) => TokenStream::new(),
+        })
+    }
+
+    /// Statements pushing the declared members to `declarations`.
+    fn declarations(&self) -> TokenStream {
+        match self {
+            InterfaceImpl::Anonymous(interface) => interface.declarations(),
+            InterfaceImpl::Named(name) => quote! {
+                <Self as #name<'a>>::__declarations(&mut declarations);
+            },
+        }
+    }
+
+    fn expand(self, struct_name: &Ident, naming: Naming, asynchronous: bool) -> TokenStream {
+        match self {
+            InterfaceImpl::Anonymous(interface) => {
+                let view = interface.view(struct_name);
+                let accessor = interface.accessor();
+                let doc = format!(" Members of `{}`.", interface.name.value());
+                let prefix = interface.signal_prefix(struct_name);
+                let error = interface.error_type(&view);
+
+                let target = Target::view(prefix.clone(), naming)
+                    .with_error(error.clone())
+                    .with_async(asynchronous);
+                let qualified = interface
+                    .members
+                    .iter()
+                    .map(|m| m.expand(&target, &interface.name));
+                let target = Target::object(prefix, naming)
+                    .with_error(error)
+                    .with_async(asynchronous);
+                let unqualified = interface
+                    .members
+                    .iter()
+                    .filter(|_| interface.alias.is_none())
+                    .map(|m| m.expand(&target, &interface.name));
+                let (generics, connection) = if asynchronous {
+                    (TokenStream::new(), TokenStream::new())
+                } else {
+                    (quote!(<C: ::dbus_client::Transport>), quote!(, C))
+                };
+                quote! {
+                    impl #generics #struct_name<'_ #connection> {
+                        #[doc = #doc]
+                        pub fn #accessor(&self) -> #view<'_ #connection> {
+                            #view(self)
+                        }
+
+                        #(#unqualified)*
+                    }
+
+                    impl #generics #view<'_ #connection> { #(#qualified)* }
+                }
+            }
+            InterfaceImpl::Named(name) => quote! {
+                impl<'a, C: ::dbus_client::Transport> #name<'a> for #struct_name<'a, C> {}
+            },
+        }
+    }
+}
+
+struct Interface {
+    // "([a-zA-Z_][a-aA-Z0-9_]*\.)+[a-zA-Z_][a-aA-Z0-9_]*"
+    name: LitStr,
+    /// `"name" as alias`, only makes the members accessible through the view.
+    alias: Option<Ident>,
+    members: Vec<Member>,
+}
+
+impl Interface {
+    /// Functions of the server trait and the expression registering this
+    /// interface, calling them on `T`.
+    ///
+    /// Members of aliased interfaces are prefixed with the alias.
+    fn server(&self, naming: Naming) -> (TokenStream, TokenStream) {
+        let interface = &self.name;
+        let function = |function: Ident| match &self.alias {
+            Some(alias) => format_ident!("{alias}_{}", function.unraw()),
+            None => function,
+        };
+        let mut functions = TokenStream::new();
+        let mut builder = TokenStream::new();
+        for member in &self.members {
+            match member {
+                Member::Method(Method {
+                    attributes,
+                    name,
+                    dbus_name,
+                    args,
+                    output,
+                    no_reply,
+                    ..
+                }) => {
+                    let function = function(naming.method(name));
+                    let names: Vec<_> = args.iter().map(|a| &a.name).collect();
+                    let types: Vec<_> = args.iter().map(|a| a.ty.wire()).collect();
+                    let arg_names = names.iter().map(|n| n.unraw().to_string());
+                    let (output, output_names, map) = match output {
+                        Type::Empty => (quote!(()), Vec::new(), None),
+                        Type::Struct(types) => (output.wire(), vec![""; types.len()], None),
+                        _ => (
+                            output.wire(),
+                            vec![""],
+                            Some(quote!(.map(|value| (value,)))),
+                        ),
+                    };
+                    let annotation = no_reply
+                        .then(|| quote!(.annotate("org.freedesktop.DBus.Method.NoReply", "true")));
+                    quote! {
+                        # use ::dbus_client::server::MethodErr;
+
+                        #(#attributes)*
+                        fn #function(&mut self, #(#names: #types),*) -> Result<#output, MethodErr>;
+                    }
+                    .to_tokens(&mut functions);
+                    quote! {
+                        builder
+                            .method(
+                                #dbus_name,
+                                (#(#arg_names,)*),
+                                (#(#output_names,)*),
+                                |_, this: &mut T, (#(#names,)*): (#(#types,)*)| {
+                                    this.#function(#(#names),*) #map
+                                },
+                            )
+                            #annotation;
+                    }
+                    .to_tokens(&mut builder);
+                }
+                Member::Property(Property {
+                    attributes,
+                    access,
+                    name,
+                    dbus_name,
+                    ty,
+                    ..
+                }) => {
+                    let ty = ty.wire();
+                    let mut property = quote!(builder.property::<#ty, _>(#dbus_name));
+                    if access.readable() {
+                        let get = function(naming.getter(name));
+                        quote! {
+                            # use ::dbus_client::server::MethodErr;
+
+                            #(#attributes)*
+                            fn #get(&self) -> Result<#ty, MethodErr>;
+                        }
+                        .to_tokens(&mut functions);
+                        property.extend(quote!(.get(|_, this: &mut T| this.#get())));
+                    }
+                    if access.writable() {
+                        let set = function(naming.setter(name));
+                        quote! {
+                            # use ::dbus_client::server::MethodErr;
+
+                            #(#attributes)*
+                            fn #set(&mut self, value: #ty) -> Result<(), MethodErr>;
+                        }
+                        .to_tokens(&mut functions);
+                        property.extend(quote! {
+                            .set(|_, this: &mut T, value| this.#set(value).map(|()| None))
+                        });
+                    }
+                    // Setters do not emit `PropertiesChanged`.
+                    quote!(#property.emits_changed_false();).to_tokens(&mut builder);
+                }
+                Member::Signal(Signal {
+                    dbus_name, args, ..
+                }) => {
+                    let types = args.iter().map(|a| a.ty.wire());
+                    let names = args.iter().map(|a| a.name.unraw().to_string());
+                    quote! {
+                        builder.signal::<(#(#types,)*), _>(#dbus_name, (#(#names,)*));
+                    }
+                    .to_tokens(&mut builder);
+                }
+                Member::Error(_) => {}
+            }
+        }
+        let param = if builder.is_empty() {
+            quote!(_)
         } else {
-            input.parse().map(Self::Anonymous)
+            quote!(builder)
+        };
+        let registration = quote! {
+            # use ::dbus_client::server::IfaceBuilder;
+
+            crossroads.register(#interface, |#param: &mut IfaceBuilder<T>| { #builder })
+        };
+        (functions, registration)
+    }
+
+    /// Statements pushing the declared members to `declarations`.
+    fn declarations(&self) -> TokenStream {
+        self.members
+            .iter()
+            .map(|m| m.declaration(&self.name))
+            .collect()
+    }
+
+    /// The signal structs, implementing `AppendAll` when they are emitted by a
+    /// server.
+    fn signals(&self, prefix: &Ident, naming: Naming, server: bool) -> TokenStream {
+        self.members
+            .iter()
+            .filter_map(|m| match m {
+                Member::Signal(signal) => {
+                    Some(signal.expand_struct(prefix, &self.name, naming, server))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Name of the error enum, `None` if the interface declares no errors.
+    fn error_type(&self, prefix: &Ident) -> Option<Ident> {
+        self.members
+            .iter()
+            .any(|m| matches!(m, Member::Error(_)))
+            .then(|| format_ident!("{prefix}Error"))
+    }
+
+    /// The error enum `name` containing the declared errors, see
+    /// [`Interface::error_type`].
+    fn errors(&self, name: Option<&Ident>) -> Result<TokenStream> {
+        let Some(name) = name else {
+            return Ok(TokenStream::new());
+        };
+        let interface = self.name.value();
+        let errors: Vec<_> = self
+            .members
+            .iter()
+            .filter_map(|m| match m {
+                Member::Error(error) => Some(error),
+                _ => None,
+            })
+            .collect();
+        if let Some(error) = errors.iter().find(|e| {
+            ["Unknown", "Transport", "Decode"].contains(&e.name.unraw().to_string().as_str())
+        }) {
+            bail!(
+                error.name,
+                "`{}` is reserved for undeclared errors and failed calls", error.name;
+                help = "rename it and specify its D-Bus name using `#[dbus(name = \"...\")]`"
+            );
+        }
+        let attributes = errors.iter().map(|e| &e.attributes);
+        let variants: Vec<_> = errors.iter().map(|e| &e.name).collect();
+        let dbus_names: Vec<_> = errors
+            .iter()
+            .map(|e| {
+                e.dbus_name.clone().unwrap_or_else(|| {
+                    LitStr::new(&format!("{interface}.{}", e.name.unraw()), e.name.span())
+                })
+            })
+            .collect();
+        let doc = format!(" Errors of `{interface}`.");
+        Ok(quote! {
+            # use ::dbus_client::__private::dbus;
+            # use ::std::fmt::{Display, Formatter};
+
+            #[doc = #doc]
+            #[derive(Debug)]
+            #[non_exhaustive]
+            pub enum #name {
+                #(#(#attributes)* #variants(String),)*
+                /// An error not declared in the interface.
+                Unknown { name: String, message: String },
+                /// The call did not reach the peer or no reply was received,
+                /// e.g., because the connection was closed or the call timed
+                /// out.
+                Transport(dbus::Error),
+                /// The reply did not match the expected types, see
+                /// [`Error::Decode`](::dbus_client::Error::Decode).
+                Decode {
+                    interface: String,
+                    member: String,
+                    expected: String,
+                    found: String,
+                    position: u32,
+                },
+            }
+
+            impl From<::dbus_client::Error> for #name {
+                fn from(error: ::dbus_client::Error) -> Self {
+                    match error {
+                        #(::dbus_client::Error::Remote { name, message } if name == #dbus_names => {
+                            Self::#variants(message)
+                        })*
+                        ::dbus_client::Error::Remote { name, message } => Self::Unknown { name, message },
+                        ::dbus_client::Error::Transport(error) => Self::Transport(error),
+                        ::dbus_client::Error::Decode { interface, member, expected, found, position } => {
+                            Self::Decode { interface, member, expected, found, position }
+                        }
+                        // `dbus_client::Error` is `non_exhaustive`.
+                        #[allow(unreachable_patterns)]
+                        error => Self::Transport(error.into()),
+                    }
+                }
+            }
+
+            impl From<#name> for ::dbus_client::Error {
+                fn from(error: #name) -> Self {
+                    match error {
+                        #(#name::#variants(message) => Self::Remote { name: #dbus_names.into(), message },)*
+                        #name::Unknown { name, message } => Self::Remote { name, message },
+                        #name::Transport(error) => Self::Transport(error),
+                        #name::Decode { interface, member, expected, found, position } => {
+                            Self::Decode { interface, member, expected, found, position }
+                        }
+                    }
+                }
+            }
+
+            impl Display for #name {
+                fn fmt(&self, f: &mut Formatter<'_>) -> ::std::fmt::Result {
+                    match self {
+                        #(Self::#variants(message) => write!(f, "{}: {message}", #dbus_names),)*
+                        Self::Unknown { name, message } => write!(f, "{name}: {message}"),
+                        Self::Transport(error) => Display::fmt(error, f),
+                        Self::Decode { interface, member, expected, found, position } => Display::fmt(
+                            &::dbus_client::Error::Decode {
+                                interface: interface.clone(),
+                                member: member.clone(),
+                                expected: expected.clone(),
+                                found: found.clone(),
+                                position: *position,
+                            },
+                            f,
+                        ),
+                    }
+                }
+            }
+
+            impl ::std::error::Error for #name {
+                fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+                    match self {
+                        Self::Transport(error) => Some(error),
+                        _ => None,
+                    }
+                }
+            }
+        })
+    }
+
+    /// Function returning the view, either the alias or `interface_{name}`.
+    fn accessor(&self) -> Ident {
+        self.alias.clone().unwrap_or_else(|| {
+            Ident::new(
+                &format!("interface_{}", self.mangled_name()),
+                self.name.span(),
+            )
+        })
+    }
+
+    /// Type of the view, i.e., `{struct_name}{Alias}` or `{struct_name}{Name}`.
+    fn view(&self, struct_name: &Ident) -> Ident {
+        let name = match &self.alias {
+            Some(alias) => alias.to_string().trim_start_matches("r#").to_owned(),
+            None => self.mangled_name(),
+        };
+        format_ident!("{struct_name}{}", camel_case(&name))
+    }
+
+    /// The name with all non alphanumeric characters replaced by `_`.
+    fn mangled_name(&self) -> String {
+        self.name
+            .value()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    /// Signals of aliased interfaces are prefixed with the view to avoid
+    /// conflicts.
+    fn signal_prefix(&self, struct_name: &Ident) -> Ident {
+        if self.alias.is_some() {
+            self.view(struct_name)
+        } else {
+            struct_name.clone()
+        }
+    }
+}
+
+impl Parse for Interface {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let alias = if input.parse::<Option<Token![as]>>()?.is_some() {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        let content;
+        braced!(content in input);
+
+        let mut members = Vec::new();
+        while !content.is_empty() {
+            members.push(content.parse()?);
+        }
+        Ok(Self {
+            name,
+            alias,
+            members,
+        })
+    }
+}
+
+enum Member {
+    Property(Property),
+    Method(Method),
+    Signal(Signal),
+    /// `!Name;`
+    Error(DbusError),
+}
+
+/// Where [`Member`]s are expanded into.
+struct Target {
+    /// Prefix of the generated signal structs.
+    name: Ident,
+    /// Visibility of the generated functions.
+    vis: TokenStream,
+    /// Receiver of the generated functions, i.e., `&self`.
+    receiver: TokenStream,
+    /// Expression evaluating to the [`DbusObject`](dbus_client::DbusObject).
+    object: TokenStream,
+    naming: Naming,
+    /// Error returned by methods, if the interface declares errors.
+    error: Option<Ident>,
+    /// Whether `object` is a `dbus_client::nonblock::DbusObject`, making the
+    /// generated functions `async`.
+    asynchronous: bool,
+    /// `DbusObject::Connection` of `object`, empty for asynchronous objects.
+    connection: TokenStream,
+}
+
+impl Target {
+    /// An inherent `impl` of a `dbus_object!`.
+    fn object(name: Ident, naming: Naming) -> Self {
+        Self {
+            name,
+            vis: quote!(pub),
+            receiver: quote!(&self),
+            object: quote!(self),
+            naming,
+            error: None,
+            asynchronous: false,
+            connection: quote!(C),
+        }
+    }
+
+    /// An inherent `impl` of a view, wrapping a reference to the object.
+    fn view(name: Ident, naming: Naming) -> Self {
+        Self {
+            object: quote!(self.0),
+            ..Self::object(name, naming)
+        }
+    }
+
+    /// The default implementations of a `dbus_interface!`.
+    fn interface(name: Ident, naming: Naming) -> Self {
+        Self {
+            name,
+            vis: TokenStream::new(),
+            receiver: quote!(&'a self),
+            object: quote!(self),
+            naming,
+            error: None,
+            asynchronous: false,
+            connection: quote!(<Self as ::dbus_client::DbusObject<'a>>::Connection),
+        }
+    }
+
+    fn with_error(self, error: Option<Ident>) -> Self {
+        Self { error, ..self }
+    }
+
+    fn with_async(self, asynchronous: bool) -> Self {
+        if !asynchronous {
+            return self;
+        }
+        Self {
+            asynchronous,
+            connection: TokenStream::new(),
+            ..self
+        }
+    }
+
+    /// Path of the `DbusObject` trait `object` implements.
+    fn dbus_object(&self) -> TokenStream {
+        if self.asynchronous {
+            quote!(::dbus_client::nonblock::DbusObject)
+        } else {
+            quote!(::dbus_client::DbusObject)
+        }
+    }
+}
+
+impl Member {
+    /// Names of the functions generated for this member.
+    fn functions(&self, naming: Naming) -> Vec<Ident> {
+        let mut functions = match self {
+            Member::Property(Property { access, name, .. }) => {
+                let mut functions = Vec::new();
+                if access.readable() {
+                    functions.push(naming.getter(name));
+                }
+                if access.writable() {
+                    functions.push(naming.setter(name));
+                }
+                functions
+            }
+            Member::Method(Method { name, .. }) => vec![naming.method(name)],
+            Member::Signal(Signal { name, .. }) => vec![naming.signal_handler(name)],
+            Member::Error(_) => Vec::new(),
+        };
+        if self.returns_object() {
+            functions.push(owned_function(&functions[0]));
         }
+        functions
     }
-}
 
-impl InterfaceImpl {
-    fn expand(self, struct_name: &Ident) -> TokenStream {
+    /// Whether this member returns [`Type::Object`]s, i.e., also generates a
+    /// function returning objects that share the connection.
+    fn returns_object(&self) -> bool {
         match self {
-            InterfaceImpl::Anonymous(Interface { name, members }) => {
-                let members = members.into_iter().map(|m| m.expand(&name));
-                quote!(impl #struct_name<'_> { #(#members)* })
+            Member::Property(Property { access, ty, .. }) => {
+                access.readable() && ty.contains_object()
             }
-            InterfaceImpl::Named(name) => quote! {
-                impl #name for #struct_name { }
-            },
+            Member::Method(Method {
+                output, no_reply, ..
+            }) => !no_reply && output.contains_object(),
+            Member::Signal(_) | Member::Error(_) => false,
         }
     }
-}
-
-struct Interface {
-    // "([a-zA-Z_][a-aA-Z0-9_]*\.)+[a-zA-Z_][a-aA-Z0-9_]*"
-    name: LitStr,
-    members: Vec<Member>,
-}
 
-impl Parse for Interface {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let name = input.parse()?;
-
-        let content;
-        braced!(content in input);
-
-        let mut members = Vec::new();
-        while !content.is_empty() {
-            members.push(content.parse()?);
+    fn expand(&self, target: &Target, interface: &LitStr) -> TokenStream {
+        let mut expansion = self.expand_variant(target, interface, false);
+        if !target.asynchronous && self.returns_object() {
+            expansion.extend(self.expand_variant(target, interface, true));
         }
-        Ok(Self { name, members })
+        expansion
     }
-}
 
-enum Member {
-    Property(Property),
-    Method(Method),
-}
-impl Member {
-    fn expand(self, interface: &LitStr) -> TokenStream {
-        let (Member::Property(Property { ty, .. }) | Member::Method(Method { output: ty, .. })) =
-            &self;
-        let mut transformer = ty.transformer();
+    /// The functions of this member, or with `owned` only the function
+    /// returning objects sharing the connection, e.g., `interfaces_owned()`.
+    fn expand_variant(&self, target: &Target, interface: &LitStr, owned: bool) -> TokenStream {
+        let Target {
+            vis,
+            receiver,
+            object,
+            naming,
+            error,
+            asynchronous,
+            connection,
+            ..
+        } = target;
+        let dbus_object = target.dbus_object();
+        let (asyncness, await_) = if *asynchronous {
+            (quote!(async), quote!(.await))
+        } else {
+            (TokenStream::new(), TokenStream::new())
+        };
+        let borrowed_args = if connection.is_empty() {
+            quote!('_)
+        } else {
+            quote!('_, #connection)
+        };
+        let (object_args, sub_object, bounds) = if owned {
+            (
+                quote!('static, #connection),
+                quote!(::dbus_client::DbusObject::shared_sub_object),
+                quote!(where #connection: Clone),
+            )
+        } else {
+            (
+                borrowed_args.clone(),
+                quote!(#dbus_object::sub_object),
+                TokenStream::new(),
+            )
+        };
+        // The owned variant is documented by linking to the borrowing one.
+        let owned_attributes = |attributes: &[Attribute], function: &Ident| {
+            if !owned {
+                return (quote!(#(#attributes)*), function.clone());
+            }
+            let doc = format!(
+                " Like [`Self::{function}`], but the returned objects share the connection \
+                 instead of borrowing `self`."
+            );
+            let attributes = attributes.iter().filter(|a| !a.path().is_ident("doc"));
+            (
+                quote!(#(#attributes)* #[doc = #doc]),
+                owned_function(function),
+            )
+        };
         match self {
             Member::Property(Property {
                 attributes,
-                mutability,
+                access,
                 name,
+                dbus_name,
                 ty,
+                timeout,
             }) => {
-                let names = name.to_string();
-                let set = if mutability.is_some() {
-                    let set = format_ident!("set_{name}");
+                let transformer = ty.transformer(object, &sub_object);
+                let wire = ty.wire();
+                let value = ty.with_object_args(&borrowed_args);
+                let ty = ty.with_object_args(&object_args);
+                let get_call = match timeout {
+                    Some(timeout) => quote! {
+                        # use std::time::Duration;
+
+                        #dbus_object::property_with_timeout::<#wire>(
+                            #object,
+                            #interface,
+                            #dbus_name,
+                            Duration::from_millis(#timeout),
+                        )
+                    },
+                    None => quote! {
+                        #dbus_object::property::<#wire>(#object, #interface, #dbus_name)
+                    },
+                };
+                let set_args = quote!((#interface, #dbus_name, ::dbus_client::__private::dbus::arg::Variant(value)));
+                let set_call = match timeout {
+                    Some(timeout) => quote! {
+                        # use std::time::Duration;
+
+                        #dbus_object::method_call_with_timeout(
+                            #object,
+                            "org.freedesktop.DBus.Properties",
+                            "Set",
+                            #set_args,
+                            Duration::from_millis(#timeout),
+                        )
+                    },
+                    None => quote! {
+                        #dbus_object::method_call(#object, "org.freedesktop.DBus.Properties", "Set", #set_args)
+                    },
+                };
+                let set = if access.writable() && !owned {
+                    let set = naming.setter(name);
                     quote! {
-                        # use dbus_client::__private::dbus::blocking::stdintf::org_freedesktop_dbus::Properties;
                         # use ::dbus_client::__private::Result;
 
                         #(#attributes)*
-                        pub fn #set(&self, value: #ty) -> Result<()> {
-                            Properties::set(self, #interface, #names, value)
+                        #vis #asyncness fn #set(#receiver, value: #value) -> Result<()> {
+                            #set_call #await_
                         }
                     }
                 } else {
                     quote!()
                 };
 
-                let get = format_ident!("get_{name}");
-                quote! {
-                    # use dbus_client::__private::dbus::blocking::stdintf::org_freedesktop_dbus::Properties;
-                    # use ::dbus_client::__private::Result;
-
-                    #set
+                let get = if access.readable() {
+                    let (attributes, get) = owned_attributes(attributes, &naming.getter(name));
+                    quote! {
+                        # use ::dbus_client::__private::Result;
 
-                    #(#attributes)*
-                    pub fn #get(&self) -> Result<#ty> {
-                        Properties::get(self, #interface, #names) #transformer
+                        #attributes
+                        #vis #asyncness fn #get(#receiver) -> Result<#ty> #bounds {
+                            #get_call #await_ #transformer
+                        }
                     }
-                }
+                } else {
+                    quote!()
+                };
+
+                quote!(#get #set)
             }
             Member::Method(Method {
                 attributes,
                 name,
+                dbus_name,
                 args,
                 output,
+                no_reply,
+                timeout,
             }) => {
-                let names = name.to_string();
+                let (attributes, function) = owned_attributes(attributes, &naming.method(name));
+                let param_names = args.iter().map(|a| &a.name);
+                let args = args.iter().map(|a| a.with_object_args(&borrowed_args));
+                let (error, map_err) = match error {
+                    Some(error) => (quote!(#error), quote!(.map_err(#error::from))),
+                    None => (quote!(::dbus_client::Error), quote!()),
+                };
+                if *no_reply {
+                    return quote! {
+                        # use ::dbus_client::__private::Result;
+
+                        #attributes
+                        #vis #asyncness fn #function(#receiver, #(#args),*) -> Result<(), #error> {
+                            #dbus_object::method_call_no_reply(#object, #interface, #dbus_name, (#(#param_names,)*)) #map_err
+                        }
+                    };
+                }
+                let mut transformer = output.transformer(object, &sub_object);
                 if !matches!(output, Type::Struct(_) | Type::Empty) {
                     transformer = Some(quote!(.map(|(v,)|v) #transformer));
                 }
-                let param_names = args.iter().map(|a| &a.name);
+                let output = output.with_object_args(&object_args);
+                let call = if let Some(timeout) = timeout {
+                    quote! {
+                        # use std::time::Duration;
+
+                        #dbus_object::method_call_with_timeout(
+                            #object,
+                            #interface,
+                            #dbus_name,
+                            (#(#param_names,)*),
+                            Duration::from_millis(#timeout),
+                        )
+                    }
+                } else {
+                    quote! {
+                        #dbus_object::method_call(#object, #interface, #dbus_name, (#(#param_names,)*))
+                    }
+                };
+                quote! {
+                    # use ::dbus_client::__private::Result;
+
+                    #attributes
+                    #vis #asyncness fn #function(#receiver, #(#args),*) -> Result<#output, #error> #bounds {
+                        #call #await_ #transformer #map_err
+                    }
+                }
+            }
+            Member::Signal(Signal {
+                attributes, name, ..
+            }) => {
+                let on = naming.signal_handler(name);
+                let signal = naming.signal_struct(&target.name, name);
+                if *asynchronous {
+                    return quote! {
+                        # use ::dbus_client::__private::Result;
+                        # use ::dbus_client::__private::dbus::nonblock::MsgMatch;
+                        # use ::dbus_client::__private::dbus::Message;
+
+                        #(#attributes)*
+                        #vis async fn #on(
+                            #receiver,
+                            f: impl FnMut(#signal, &Message) -> bool + Send + 'static,
+                        ) -> Result<MsgMatch> {
+                            #dbus_object::match_signal(#object, f).await
+                        }
+                    };
+                }
                 quote! {
                     # use ::dbus_client::__private::Result;
-                    # use ::dbus_client::DbusObject;
+                    # use ::dbus_client::__private::dbus::channel::Token;
+                    # use ::dbus_client::__private::dbus::Message;
 
                     #(#attributes)*
-                    pub fn #name(&self, #(#args),*) -> Result<#output> {
-                        DbusObject::method_call(self, #interface, #names, (#(#param_names,)*)) #transformer
+                    #vis fn #on(
+                        #receiver,
+                        f: impl FnMut(#signal, &#connection, &Message) -> bool + Send + 'static,
+                    ) -> Result<Token> {
+                        #dbus_object::match_signal(#object, f)
+                    }
+                }
+            }
+            Member::Error(_) => TokenStream::new(),
+        }
+    }
+}
+
+impl Member {
+    /// Statement pushing the [`Declaration`](dbus_client::verify::Declaration)
+    /// of this member to `declarations`.
+    fn declaration(&self, interface: &LitStr) -> TokenStream {
+        let declaration = match self {
+            Member::Property(Property {
+                access,
+                dbus_name,
+                ty,
+                ..
+            }) => {
+                let ty = signature([ty]);
+                let access = match access {
+                    Access::Read => quote!(Read),
+                    Access::Write => quote!(Write),
+                    Access::ReadWrite => quote!(ReadWrite),
+                };
+                quote! {
+                    Property {
+                        interface: #interface,
+                        name: #dbus_name,
+                        ty: #ty,
+                        access: ::dbus_client::verify::Access::#access,
+                    }
+                }
+            }
+            Member::Method(Method {
+                dbus_name,
+                args,
+                output,
+                ..
+            }) => {
+                let args = signature(args.iter().map(|a| &a.ty));
+                let output = match output {
+                    Type::Struct(types) => signature(types),
+                    Type::Empty => signature([]),
+                    output => signature([output]),
+                };
+                quote! {
+                    Method {
+                        interface: #interface,
+                        name: #dbus_name,
+                        args: #args,
+                        output: #output,
                     }
                 }
             }
+            Member::Signal(Signal {
+                dbus_name, args, ..
+            }) => {
+                let args = signature(args.iter().map(|a| &a.ty));
+                quote! {
+                    Signal {
+                        interface: #interface,
+                        name: #dbus_name,
+                        args: #args,
+                    }
+                }
+            }
+            Member::Error(_) => return TokenStream::new(),
+        };
+        quote! {
+            declarations.push(::dbus_client::verify::Declaration::#declaration);
         }
     }
 }
 
+/// Expression evaluating to the concatenated signatures of `types`, `None` if
+/// any of them does not implement `Arg`.
+fn signature<'a>(types: impl IntoIterator<Item = &'a Type>) -> TokenStream {
+    let types = types.into_iter().map(Type::wire);
+    quote! {{
+        use ::dbus_client::__private::{KnownSignature as _, UnknownSignature as _};
+        use ::dbus_client::__private::SignatureOf;
+
+        ::dbus_client::__private::concat_signatures(&[
+            #((&SignatureOf::<#types>(::std::marker::PhantomData)).signature(),)*
+        ])
+    }}
+}
+
 impl Parse for Member {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let attributes = Attribute::parse_outer(input)?;
-        let mutability: Option<_> = input.parse()?;
-        let name = input.parse()?;
+        let mut attributes = Attribute::parse_outer(input)?;
+        let options = MemberOptions::remove_attributes(&mut attributes)?;
+        let dbus_name = |name: &Ident| {
+            options
+                .name
+                .clone()
+                .unwrap_or_else(|| LitStr::new(&name.unraw().to_string(), name.span()))
+        };
+        let only_methods = |name: &Ident| {
+            if options.no_reply {
+                Err(syn::Error::new(
+                    name.span(),
+                    "`no_reply` is only supported on methods",
+                ))
+            } else {
+                Ok(())
+            }
+        };
+        let timeout = options.timeout.as_ref().map(timeout_millis).transpose()?;
+        if input.parse::<Option<Token![!]>>()?.is_some() {
+            let name: Ident = input.parse()?;
+            only_methods(&name)?;
+            if let Some(timeout) = &options.timeout {
+                return Err(syn::Error::new(
+                    timeout.span(),
+                    "`timeout` is not supported on errors",
+                ));
+            }
+            input.parse::<Token![;]>()?;
+            return Ok(Self::Error(DbusError {
+                attributes,
+                name,
+                dbus_name: options.name,
+            }));
+        }
+        if input.parse::<Option<Token![~]>>()?.is_some() {
+            let name: Ident = input.parse()?;
+            only_methods(&name)?;
+            if let Some(timeout) = &options.timeout {
+                return Err(syn::Error::new(
+                    timeout.span(),
+                    "`timeout` is not supported on signals",
+                ));
+            }
+            let content;
+            parenthesized!(content in input);
+            let args = Punctuated::<Arg, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect();
+            input.parse::<Token![;]>()?;
+            return Ok(Self::Signal(Signal {
+                attributes,
+                dbus_name: dbus_name(&name),
+                name,
+                args,
+            }));
+        }
+        let access = if input.parse::<Option<Token![mut]>>()?.is_some() {
+            Access::ReadWrite
+        } else if input.peek(member::write) && input.peek2(Ident) {
+            input.parse::<member::write>()?;
+            Access::Write
+        } else {
+            Access::Read
+        };
+        let name: Ident = input.parse()?;
 
         let la = input.lookahead1();
-        if mutability.is_some() || la.peek(Token![:]) {
+        if access != Access::Read || la.peek(Token![:]) {
+            only_methods(&name)?;
             input.parse::<Token![:]>()?;
             let ty = input.parse()?;
             input.parse::<Token![;]>()?;
             Ok(Self::Property(Property {
                 attributes,
-                mutability,
+                access,
+                dbus_name: dbus_name(&name),
                 name,
                 ty,
+                timeout,
             }))
         } else if la.peek(Paren) {
             let content;
             parenthesized!(content in input);
-            let mut args = Vec::new();
-            while !content.is_empty() {
-                args.push(content.parse()?);
-            }
+            let args = Punctuated::<Arg, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect();
 
-            let output = if input.parse::<Token![->]>().is_ok() {
-                input.parse()?
-            } else {
+            let output = if input.parse::<Token![->]>().is_err() {
                 Type::Empty
+            } else if input.peek(LitStr) {
+                // Signatures can contain multiple return values, e.g., `-> "su"`.
+                let mut types = Type::from_signature(&input.parse()?)?;
+                match types.len() {
+                    0 => Type::Empty,
+                    // `Type::Struct` lists the return values, i.e., `-> "(su)"`
+                    // is `-> ((s u))`.
+                    1 if matches!(types[0], Type::Struct(_)) => Type::Struct(types),
+                    1 => types.remove(0),
+                    _ => Type::Struct(types),
+                }
+            } else {
+                input.parse()?
             };
 
             input.parse::<Token![;]>()?;
 
+            if options.no_reply && !matches!(output, Type::Empty) {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "methods with `no_reply` cannot return values",
+                ));
+            }
+            if let (true, Some(timeout)) = (options.no_reply, &options.timeout) {
+                return Err(syn::Error::new(
+                    timeout.span(),
+                    "`timeout` has no effect on methods with `no_reply`",
+                ));
+            }
+
             Ok(Self::Method(Method {
                 attributes,
+                dbus_name: dbus_name(&name),
                 name,
                 args,
                 output,
+                no_reply: options.no_reply,
+                timeout,
             }))
         } else {
             Err(la.error())
@@ -463,11 +2149,29 @@ impl Parse for Member {
     }
 }
 
+/// `#[dbus(...)]` on a member.
+#[derive(FromAttr)]
+#[attribute(ident = dbus)]
+struct MemberOptions {
+    /// Name of the member on the bus, defaults to the name in the macro.
+    name: Option<LitStr>,
+    /// Don't wait for a reply, only supported on methods without return
+    /// value.
+    no_reply: bool,
+    /// Timeout overriding the one of the object, i.e., `500ms`, `5s` or
+    /// `2min`.
+    timeout: Option<LitInt>,
+}
+
 struct Property {
-    mutability: Option<Token![mut]>,
+    /// `Name: t;`, `mut Name: t;` or `write Name: t;`.
+    access: Access,
     name: Ident,
+    dbus_name: LitStr,
     ty: Type,
     attributes: Vec<Attribute>,
+    /// `#[dbus(timeout = 5s)]` in milliseconds.
+    timeout: Option<u64>,
 }
 
 mod types {
@@ -479,6 +2183,8 @@ mod types {
     custom_keyword!(q);
     custom_keyword!(i);
     custom_keyword!(u);
+    custom_keyword!(x);
+    custom_keyword!(t);
     custom_keyword!(d);
     custom_keyword!(h);
     custom_keyword!(s);
@@ -495,8 +2201,10 @@ enum SimpleType {
     U16,
     I32,
     U32,
+    I64,
+    U64,
     F64,
-    File,
+    Fd,
     String,
     Path,
     Signature,
@@ -511,16 +2219,52 @@ impl ToTokens for SimpleType {
             SimpleType::U16 => quote!(u16),
             SimpleType::I32 => quote!(i32),
             SimpleType::U32 => quote!(u32),
+            SimpleType::I64 => quote!(i64),
+            SimpleType::U64 => quote!(u64),
             SimpleType::F64 => quote!(f64),
-            SimpleType::File => quote!(::std::fs::File),
+            SimpleType::Fd => quote!(::dbus_client::__private::dbus::arg::OwnedFd),
             SimpleType::String => quote!(String),
-            SimpleType::Path => quote!(::dbus_client::__private::dbus::strings::Path),
-            SimpleType::Signature => quote!(::dbus_client::__private::dbus::strings::Signature),
+            SimpleType::Path => quote!(::dbus_client::__private::dbus::strings::Path<'_>),
+            SimpleType::Signature => {
+                quote!(::dbus_client::__private::dbus::strings::Signature<'_>)
+            }
         }
         .to_tokens(tokens)
     }
 }
 
+impl SimpleType {
+    fn from_code(code: char) -> Option<Self> {
+        Some(match code {
+            'y' => SimpleType::U8,
+            'b' => SimpleType::Bool,
+            'n' => SimpleType::I16,
+            'q' => SimpleType::U16,
+            'i' => SimpleType::I32,
+            'u' => SimpleType::U32,
+            'x' => SimpleType::I64,
+            't' => SimpleType::U64,
+            'd' => SimpleType::F64,
+            'h' => SimpleType::Fd,
+            's' => SimpleType::String,
+            'o' => SimpleType::Path,
+            'g' => SimpleType::Signature,
+            _ => return None,
+        })
+    }
+
+    /// The type as it is read from the wire, i.e., with `'static` lifetimes.
+    fn wire(&self) -> TokenStream {
+        match self {
+            SimpleType::Path => quote!(::dbus_client::__private::dbus::strings::Path<'static>),
+            SimpleType::Signature => {
+                quote!(::dbus_client::__private::dbus::strings::Signature<'static>)
+            }
+            _ => self.to_token_stream(),
+        }
+    }
+}
+
 impl Parse for SimpleType {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         use types::*;
@@ -537,10 +2281,14 @@ impl Parse for SimpleType {
             SimpleType::I32
         } else if la.peek(u) {
             SimpleType::U32
+        } else if la.peek(x) {
+            SimpleType::I64
+        } else if la.peek(t) {
+            SimpleType::U64
         } else if la.peek(d) {
             SimpleType::F64
         } else if la.peek(h) {
-            SimpleType::File
+            SimpleType::Fd
         } else if la.peek(s) {
             SimpleType::String
         } else if la.peek(o) {
@@ -562,50 +2310,197 @@ enum Type {
     Simple(SimpleType),
     Struct(Vec<Type>),
     Array(Box<Type>),
-    Map(SimpleType, Box<Type>),
+    /// Keys are either [`Type::Simple`] or [`Type::Object`].
+    Map(Box<Type>, Box<Type>),
     Empty,
 }
 impl Type {
-    fn transformer(&self) -> Option<TokenStream> {
+    /// Whether this type contains [`Type::Object`]s.
+    fn contains_object(&self) -> bool {
         match self {
-            Type::Object(..) => Some(
-                quote!(.map(|path: ::dbus_client::__private::dbus::strings::Path| ::dbus_client::DbusObject::sub_object(self, path))),
-            ),
-            Type::Struct(_) => todo!(),
+            Type::Object(..) => true,
+            Type::Struct(types) => types.iter().any(Type::contains_object),
+            Type::Array(ty) => ty.contains_object(),
+            Type::Map(k, v) => k.contains_object() || v.contains_object(),
+            _ => false,
+        }
+    }
+
+    /// `.map(...)` converting the value read from the wire into this type,
+    /// `None` if no conversion is necessary.
+    fn transformer(&self, object: &TokenStream, sub_object: &TokenStream) -> Option<TokenStream> {
+        let wire = self.wire();
+        self.convert(quote!(value), object, sub_object)
+            .map(|convert| quote!(.map(|value: #wire| #convert)))
+    }
+
+    /// Expression converting `value` read from the wire into this type, `None`
+    /// if no conversion is necessary.
+    ///
+    /// Sub-objects are created from `object` using the function `sub_object`,
+    /// e.g., `DbusObject::sub_object`.
+    fn convert(
+        &self,
+        value: TokenStream,
+        object: &TokenStream,
+        sub_object: &TokenStream,
+    ) -> Option<TokenStream> {
+        match self {
+            Type::Object(..) => Some(quote!(#sub_object(#object, #value))),
+            Type::Struct(types) => {
+                let names: Vec<_> = (0..types.len()).map(|i| format_ident!("v{i}")).collect();
+                let converted: Vec<_> = types
+                    .iter()
+                    .zip(&names)
+                    .map(|(ty, name)| ty.convert(name.to_token_stream(), object, sub_object))
+                    .collect();
+                if converted.iter().all(Option::is_none) {
+                    return None;
+                }
+                let converted = converted
+                    .into_iter()
+                    .zip(&names)
+                    .map(|(converted, name)| converted.unwrap_or_else(|| name.to_token_stream()));
+                Some(quote!({
+                    let (#(#names,)*) = #value;
+                    (#(#converted,)*)
+                }))
+            }
             Type::Array(ty) => ty
-                .transformer()
-                .map(|sub| quote!(.map(|value: Vec<_>| value.into_iter()#sub.collect()))),
-            Type::Map(..) => todo!(),
+                .convert(quote!(value), object, sub_object)
+                .map(|convert| quote!(#value.into_iter().map(|value| #convert).collect())),
+            Type::Map(k, v) => {
+                let convert_key = k.convert(quote!(key), object, sub_object);
+                let convert_value = v.convert(quote!(value), object, sub_object);
+                if convert_key.is_none() && convert_value.is_none() {
+                    return None;
+                }
+                let convert_key = convert_key.unwrap_or_else(|| quote!(key));
+                let convert_value = convert_value.unwrap_or_else(|| quote!(value));
+                Some(quote! {
+                    #value.into_iter().map(|(key, value)| (#convert_key, #convert_value)).collect()
+                })
+            }
             _ => None,
         }
     }
 }
 
+impl Type {
+    /// The type as it is read from the wire, i.e., with [`Type::Object`]s
+    /// replaced by owned [`Path`](dbus::strings::Path)s.
+    fn wire(&self) -> TokenStream {
+        match self {
+            Type::Simple(simple) => simple.wire(),
+            Type::Object(..) => {
+                quote!(::dbus_client::__private::dbus::strings::Path<'static>)
+            }
+            Type::Struct(t) => {
+                let t = t.iter().map(Type::wire);
+                quote!((#(#t,)*))
+            }
+            Type::Array(t) => {
+                let t = t.wire();
+                quote!(Vec<#t>)
+            }
+            Type::Map(k, v) => {
+                let k = k.wire();
+                let v = v.wire();
+                quote!(::std::collections::HashMap<#k, #v>)
+            }
+            _ => self.to_token_stream(),
+        }
+    }
+}
+
+impl From<&signature::Type> for Type {
+    fn from(value: &signature::Type) -> Self {
+        match value {
+            signature::Type::Basic(code) => Type::Simple(simple_type(*code)),
+            signature::Type::Variant => Type::Variant,
+            signature::Type::Array(ty) => Type::Array(Box::new(ty.as_ref().into())),
+            signature::Type::Dict(key, value) => Type::Map(
+                Box::new(Type::Simple(simple_type(*key))),
+                Box::new(value.as_ref().into()),
+            ),
+            signature::Type::Struct(types) => Type::Struct(types.iter().map(Into::into).collect()),
+            signature::Type::Object(name) => Type::Object(
+                Default::default(),
+                syn::parse_str(name).expect("object types are valid paths"),
+            ),
+            signature::Type::Named(name, _) => {
+                Type::Rust(syn::parse_str(name).expect("named types are valid paths"))
+            }
+        }
+    }
+}
+
+fn simple_type(code: char) -> SimpleType {
+    SimpleType::from_code(code).expect("signature only contains supported basic types")
+}
+
 impl ToTokens for Type {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.with_object_args(&quote!('_)).to_tokens(tokens);
+    }
+}
+
+impl Type {
+    /// The type with `args` as generic arguments of [`Type::Object`]s, e.g.,
+    /// `'_, C` for `Object<'_, C>`.
+    fn with_object_args(&self, args: &TokenStream) -> TokenStream {
         match self {
             Type::Variant => quote!(
                 ::dbus_client::__private::dbus::arg::Variant<
                     Box<dyn ::dbus_client::__private::dbus::arg::RefArg + 'static>,
                 >
-            )
-            .to_tokens(tokens),
-            Type::Rust(path) => path.to_tokens(tokens),
-            Type::Simple(simple) => simple.to_tokens(tokens),
-            Type::Struct(t) => quote!((#(#t,)*)).to_tokens(tokens),
-            Type::Array(t) => quote!(Vec<#t>).to_tokens(tokens),
-            Type::Map(k, v) => quote!(::std::collections::HashMap<#k, #v>).to_tokens(tokens),
-            Type::Empty => quote!(()).to_tokens(tokens),
-            Type::Object(_, path) => path.to_tokens(tokens),
+            ),
+            Type::Rust(path) => path.to_token_stream(),
+            Type::Simple(simple) => simple.to_token_stream(),
+            Type::Struct(t) => {
+                let t = t.iter().map(|t| t.with_object_args(args));
+                quote!((#(#t,)*))
+            }
+            Type::Array(t) => {
+                let t = t.with_object_args(args);
+                quote!(Vec<#t>)
+            }
+            Type::Map(k, v) => {
+                let k = k.with_object_args(args);
+                let v = v.with_object_args(args);
+                quote!(::std::collections::HashMap<#k, #v>)
+            }
+            Type::Empty => quote!(()),
+            // Elided lifetimes are not allowed in arguments of `async fn`s.
+            Type::Object(_, path) => quote!(#path<#args>),
         }
     }
 }
 
+impl Type {
+    /// Parses a D-Bus signature literal, e.g., `"a{sv}"`.
+    fn from_signature(signature: &LitStr) -> syn::Result<Vec<Self>> {
+        signature::Type::parse_all(&signature.value())
+            .map(|types| types.iter().map(Into::into).collect())
+            .map_err(|e| syn::Error::new_spanned(signature, format!("invalid signature: {e}")))
+    }
+}
+
 impl Parse for Type {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         use types::*;
         let la = input.lookahead1();
-        Ok(if la.peek(Paren) {
+        Ok(if la.peek(LitStr) {
+            let signature: LitStr = input.parse()?;
+            let mut types = Self::from_signature(&signature)?;
+            if types.len() != 1 {
+                return Err(syn::Error::new_spanned(
+                    signature,
+                    "signature must be a single complete type",
+                ));
+            }
+            types.remove(0)
+        } else if la.peek(Paren) {
             let content;
             parenthesized!(content in input);
             let mut types = Vec::new();
@@ -618,7 +2513,12 @@ impl Parse for Type {
             if input.peek(Brace) {
                 let content;
                 braced!(content in input);
-                Type::Map(content.parse()?, content.parse()?)
+                let key = if content.peek(Token![@]) {
+                    Type::Object(content.parse()?, content.parse()?)
+                } else {
+                    Type::Simple(content.parse()?)
+                };
+                Type::Map(Box::new(key), content.parse()?)
             } else {
                 Type::Array(input.parse()?)
             }
@@ -640,10 +2540,14 @@ impl Parse for Type {
                 SimpleType::I32
             } else if la.peek(u) {
                 SimpleType::U32
+            } else if la.peek(x) {
+                SimpleType::I64
+            } else if la.peek(t) {
+                SimpleType::U64
             } else if la.peek(d) {
                 SimpleType::F64
             } else if la.peek(h) {
-                SimpleType::File
+                SimpleType::Fd
             } else if la.peek(s) {
                 SimpleType::String
             } else if la.peek(o) {
@@ -664,8 +2568,86 @@ impl Parse for Type {
 struct Method {
     attributes: Vec<Attribute>,
     name: Ident,
+    dbus_name: LitStr,
     args: Vec<Arg>,
     output: Type,
+    /// `#[dbus(no_reply)]`
+    no_reply: bool,
+    /// `#[dbus(timeout = 5s)]` in milliseconds.
+    timeout: Option<u64>,
+}
+
+/// An error that can be returned by the methods of an interface.
+struct DbusError {
+    attributes: Vec<Attribute>,
+    name: Ident,
+    /// Defaults to `{interface}.{name}`.
+    dbus_name: Option<LitStr>,
+}
+
+struct Signal {
+    attributes: Vec<Attribute>,
+    name: Ident,
+    dbus_name: LitStr,
+    args: Vec<Arg>,
+}
+
+impl Signal {
+    fn expand_struct(
+        &self,
+        struct_name: &Ident,
+        interface: &LitStr,
+        naming: Naming,
+        server: bool,
+    ) -> TokenStream {
+        let Self {
+            attributes,
+            name,
+            dbus_name,
+            args,
+        } = self;
+        let signal = naming.signal_struct(struct_name, name);
+        let fields = args.iter().map(|a| &a.name);
+        let field_types = args.iter().map(|a| a.ty.wire());
+        let reads = fields.clone();
+        let append = server.then(|| {
+            let fields = fields.clone();
+            quote! {
+                # use ::dbus_client::__private::dbus::arg::{Append, AppendAll, IterAppend};
+
+                impl AppendAll for #signal {
+                    fn append(&self, __i: &mut IterAppend) {
+                        #(Append::append_by_ref(&self.#fields, __i);)*
+                    }
+                }
+            }
+        });
+        quote! {
+            # use ::dbus_client::__private::dbus::arg::{Iter, ReadAll, TypeMismatchError};
+            # use ::dbus_client::__private::dbus::message::SignalArgs;
+
+            #(#attributes)*
+            #[allow(non_snake_case)]
+            pub struct #signal {
+                #(pub #fields: #field_types,)*
+            }
+
+            impl SignalArgs for #signal {
+                const NAME: &'static str = #dbus_name;
+                const INTERFACE: &'static str = #interface;
+            }
+
+            impl ReadAll for #signal {
+                fn read(__i: &mut Iter) -> Result<Self, TypeMismatchError> {
+                    Ok(Self {
+                        #(#reads: __i.read()?,)*
+                    })
+                }
+            }
+
+            #append
+        }
+    }
 }
 
 struct Arg {
@@ -686,8 +2668,252 @@ impl Parse for Arg {
 
 impl ToTokens for Arg {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.with_object_args(&quote!('_)).to_tokens(tokens);
+    }
+}
+
+impl Arg {
+    /// See [`Type::with_object_args`].
+    fn with_object_args(&self, args: &TokenStream) -> TokenStream {
         let Self { name, ty } = self;
+        let ty = ty.with_object_args(args);
+        quote!(#name: #ty)
+    }
+}
+
+#[test]
+fn signature_output() {
+    let Member::Method(method) = syn::parse_str(r#"Get() -> "(su)";"#).unwrap() else {
+        panic!("expected a method");
+    };
+    let Type::Struct(outputs) = &method.output else {
+        panic!("expected a list of return values");
+    };
+    assert!(matches!(outputs.as_slice(), [Type::Struct(types)] if types.len() == 2));
+    assert_eq!(
+        method.output.with_object_args(&quote!('_)).to_string(),
+        "((:: std :: prelude :: v1 :: String , u32 ,) ,)"
+    );
+
+    let Member::Method(method) = syn::parse_str(r#"Get() -> "su";"#).unwrap() else {
+        panic!("expected a method");
+    };
+    assert_eq!(
+        method.output.with_object_args(&quote!('_)).to_string(),
+        "(:: std :: prelude :: v1 :: String , u32 ,)"
+    );
+
+    let Err(error) = syn::parse_str::<Member>(r#"Get(a: "a{vs}");"#) else {
+        panic!("expected an error");
+    };
+    assert_eq!(
+        error.to_string(),
+        "invalid signature: dict entry keys must be basic types"
+    );
+}
+
+/// The message of the error returned by [`dbus_object`] for `input`.
+#[cfg(test)]
+fn error(input: &str) -> String {
+    use manyhow::ToTokensError;
+    let Err(error) = dbus_object(syn::parse_str(input).unwrap()) else {
+        panic!("expected an error");
+    };
+    let message = error
+        .into_token_stream()
+        .into_iter()
+        .find_map(|token| match token {
+            proc_macro2::TokenTree::Group(group) => Some(group.stream()),
+            _ => None,
+        })
+        .unwrap();
+    syn::parse2::<LitStr>(message)
+        .unwrap()
+        .value()
+        .trim_end()
+        .to_owned()
+}
+
+#[test]
+fn conflicting_members() {
+    assert_eq!(
+        error(
+            r#"Device("org.example", "/org/example/Device")
+            "org.example.Device" { State: u; }
+            "org.example.Device.Legacy" { State: s; }"#
+        ),
+        "`state` is defined by both `org.example.Device` and `org.example.Device.Legacy`\n\n  = \
+         help: add an alias, i.e., `\"org.example.Device.Legacy\" as alias { ... }`, to only \
+         access its members through `alias()`"
+    );
+    assert!(
+        error(
+            r#"Device("org.example", "/org/example/Device")
+        "org.example.Device" { State: u; state: s; }"#
+        )
+        .starts_with("`state` is defined multiple times in `org.example.Device`")
+    );
+}
+
+#[test]
+fn reserved_error_names() {
+    for name in ["Unknown", "Transport", "Decode"] {
+        assert_eq!(
+            error(&format!(
+                r#"Device("org.example", "/org/example/Device")
+                "org.example.Device" {{ Reset(); !{name}; }}"#
+            )),
+            format!(
+                "`{name}` is reserved for undeclared errors and failed calls\n\n  = help: rename \
+                 it and specify its D-Bus name using `#[dbus(name = \"...\")]`"
+            )
+        );
+    }
+    assert!(dbus_object(
+        syn::parse_str(
+            r#"Device("org.example", "/org/example/Device")
+            "org.example.Device" { Reset(); #[dbus(name = "org.example.Device.Transport")] !TransportFailed; }"#
+        )
+        .unwrap()
+    )
+    .is_ok());
+}
+
+#[test]
+fn naming_conflicts() {
+    const MEMBERS: &str = r#"Device("org.example", "/org/example/Device")
+        "org.example.Device" { GetID() -> s; GetId() -> s; }"#;
+    assert_eq!(
+        error(MEMBERS),
+        "`get_id` is defined multiple times in `org.example.Device`\n\n  = help: rename one of \
+         them and specify its D-Bus name using `#[dbus(name = \"...\")]`"
+    );
+    assert!(
+        dbus_object(syn::parse_str(&format!("#[dbus(legacy_naming)] {MEMBERS}")).unwrap()).is_ok()
+    );
+    assert!(
+        error(
+            r#"Device("org.example", "/org/example/Device")
+        "org.example.Device" { #[dbus(name = "GetID")] get_id() -> s; GetId() -> s; }"#
+        )
+        .starts_with("`get_id` is defined multiple times")
+    );
+    assert!(
+        dbus_object(
+            syn::parse_str(
+                r#"Device("org.example", "/org/example/Device")
+            "org.example.Device" { #[dbus(name = "GetID")] get_dbus_id() -> s; GetId() -> s; }"#
+            )
+            .unwrap()
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn write_only_functions() {
+    let functions = |member: &str| {
+        syn::parse_str::<Member>(member)
+            .unwrap()
+            .functions(Naming::Rust)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(functions("Password: s;"), ["password"]);
+    assert_eq!(functions("mut Password: s;"), ["password", "set_password"]);
+    assert_eq!(functions("write Password: s;"), ["set_password"]);
+}
+
+#[test]
+fn no_reply_diagnostics() {
+    let error = |member: &str| syn::parse_str::<Member>(member).err().unwrap().to_string();
+    assert_eq!(
+        error("#[dbus(no_reply)] Next() -> u;"),
+        "methods with `no_reply` cannot return values"
+    );
+    assert_eq!(
+        error("#[dbus(no_reply)] Position: x;"),
+        "`no_reply` is only supported on methods"
+    );
+    assert_eq!(
+        error("#[dbus(no_reply, timeout = 5s)] Next();"),
+        "`timeout` has no effect on methods with `no_reply`"
+    );
+}
+
+#[test]
+fn timeout_diagnostics() {
+    let timeout = |timeout: &str| timeout_millis(&syn::parse_str(timeout).unwrap());
+    assert_eq!(timeout("500ms").unwrap(), 500);
+    assert_eq!(timeout("5s").unwrap(), 5000);
+    assert_eq!(timeout("2min").unwrap(), 120_000);
+    for invalid in ["5", "5h", "5sec"] {
+        assert_eq!(
+            timeout(invalid).unwrap_err().to_string(),
+            "expected a duration, i.e., `500ms`, `5s` or `2min`"
+        );
+    }
+    assert_eq!(
+        timeout("18446744073709551615min").unwrap_err().to_string(),
+        "timeout is too large"
+    );
+
+    assert_eq!(
+        error(r#"Device("org.example", "/org/example/Device", timeout = 5h)"#),
+        "expected a duration, i.e., `500ms`, `5s` or `2min`"
+    );
+    assert_eq!(
+        syn::parse_str::<Member>("#[dbus(timeout = 5)] Scan();")
+            .err()
+            .unwrap()
+            .to_string(),
+        "expected a duration, i.e., `500ms`, `5s` or `2min`"
+    );
+    assert_eq!(
+        syn::parse_str::<Member>("#[dbus(timeout = 5s)] ~Changed();")
+            .err()
+            .unwrap()
+            .to_string(),
+        "`timeout` is not supported on signals"
+    );
+}
 
-        quote!(#name: #ty).to_tokens(tokens);
+#[test]
+fn async_objects() {
+    const BUS: &str = r#"Bus("org.freedesktop.DBus", "/org/freedesktop/DBus", async)
+        "org.freedesktop.DBus" { GetId() -> s; Features: a s; ~NameAcquired(name: s); }"#;
+    let expanded = dbus_object(syn::parse_str(BUS).unwrap())
+        .unwrap()
+        .to_string();
+    for function in ["get_id", "features", "on_name_acquired"] {
+        assert!(
+            expanded.contains(&format!("pub async fn {function} (")),
+            "`{function}` is not async"
+        );
     }
+    assert!(expanded.contains("nonblock :: DbusObject"));
+
+    assert_eq!(
+        error(r#"Bus("org.freedesktop.DBus", "/org/freedesktop/DBus", session, async)"#),
+        "`session` and `system` are not supported for async objects\n\n  = help: connect using \
+         `dbus-tokio`, i.e., `dbus_tokio::connection::new_session_sync()`"
+    );
+    assert_eq!(
+        error(r#"Bus("org.freedesktop.DBus", "/org/freedesktop/DBus", async) Peer;"#),
+        "`dbus_interface!`s are not supported for async objects"
+    );
+    assert_eq!(
+        error(r#"#[dbus(mock)] Bus("org.freedesktop.DBus", "/org/freedesktop/DBus", async)"#),
+        "`mock` is not supported for async objects"
+    );
+    assert_eq!(
+        syn::parse_str::<Object>(
+            r#"Bus("org.freedesktop.DBus", "/org/freedesktop/DBus", async, async)"#
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        "async was already set"
+    );
 }
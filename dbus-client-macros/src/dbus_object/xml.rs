@@ -0,0 +1,113 @@
+use std::path::PathBuf;
+
+use dbus_client_codegen::introspect::{self, Node, doc_lines};
+use syn::parse::Parser;
+
+use super::*;
+
+pub fn dbus_object_from_xml(FromXml { file, mut object }: FromXml) -> Result {
+    let path =
+        PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default()).join(file.value());
+    let xml = std::fs::read_to_string(&path)
+        .map_err(|e| error_message!(file, "unable to read `{}`: {e}", path.display()))?;
+    let node = Node::from_xml(&xml).map_err(|e| error_message!(file, "{e}"))?;
+
+    for interface in &node.interfaces {
+        object
+            .interfaces
+            .push(InterfaceImpl::Anonymous(Interface::from_xml(
+                interface,
+                file.span(),
+            )?));
+    }
+
+    let expansion = dbus_object(object)?;
+    let path = path.display().to_string();
+    // Makes cargo recompile when the XML changes.
+    Ok(quote! {
+        #expansion
+        const _: &[u8] = include_bytes!(#path);
+    })
+}
+
+pub struct FromXml {
+    file: LitStr,
+    object: Object,
+}
+
+impl Parse for FromXml {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let file = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let object = Object::parse_header(input)?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self { file, object })
+    }
+}
+
+impl Interface {
+    fn from_xml(interface: &introspect::Interface, span: Span) -> syn::Result<Self> {
+        let args = |args: &[introspect::Arg]| {
+            args.iter()
+                .enumerate()
+                .map(|(idx, arg)| {
+                    Ok(Arg {
+                        name: ident(&arg.rust_name(idx), span),
+                        ty: (&arg.ty).into(),
+                    })
+                })
+                .collect::<syn::Result<_>>()
+        };
+
+        let members = interface
+            .members
+            .iter()
+            .map(|member| {
+                Ok(match member {
+                    introspect::Member::Method(method) => Member::Method(Method {
+                        attributes: docs(doc_lines(&method.annotations, &method.args))?,
+                        name: ident(&rust_ident(&method.name), span),
+                        dbus_name: LitStr::new(&method.name, span),
+                        args: args(&method.args)?,
+                        output: match method.outputs.as_slice() {
+                            [] => Type::Empty,
+                            [output] if !matches!(output.ty, signature::Type::Struct(_)) => {
+                                (&output.ty).into()
+                            }
+                            outputs => {
+                                Type::Struct(outputs.iter().map(|o| (&o.ty).into()).collect())
+                            }
+                        },
+                        no_reply: method.no_reply(),
+                        timeout: None,
+                    }),
+                    introspect::Member::Property(property) => Member::Property(Property {
+                        attributes: docs(doc_lines(&property.annotations, &[]))?,
+                        access: property.access,
+                        name: ident(&rust_ident(&property.name), span),
+                        dbus_name: LitStr::new(&property.name, span),
+                        ty: (&property.ty).into(),
+                        timeout: None,
+                    }),
+                    introspect::Member::Signal(signal) => Member::Signal(Signal {
+                        attributes: docs(doc_lines(&signal.annotations, &signal.args))?,
+                        name: ident(&rust_ident(&signal.name), span),
+                        dbus_name: LitStr::new(&signal.name, span),
+                        args: args(&signal.args)?,
+                    }),
+                })
+            })
+            .collect::<syn::Result<_>>()?;
+
+        Ok(Self {
+            name: LitStr::new(&interface.name, span),
+            alias: None,
+            members,
+        })
+    }
+}
+
+fn docs(lines: Vec<String>) -> syn::Result<Vec<Attribute>> {
+    let lines = lines.into_iter().map(|line| format!(" {line}"));
+    Attribute::parse_outer.parse2(quote!(#(#[doc = #lines])*))
+}
@@ -19,7 +19,24 @@ pub fn append(
             fields: Fields::Named(fields),
             ..
         }) => named_struct(fields.named, value_signature),
-        Data::Struct(_) => todo!(),
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(fields),
+            ..
+        }) => {
+            if let Some(value_signature) = value_signature {
+                bail!(
+                    value_signature.0,
+                    "`value_signature` is only supported on structs with named fields"
+                );
+            }
+            let fields = (0..fields.unnamed.len()).map(syn::Index::from);
+            quote! {
+                __i.append_struct(|__i| {
+                    #(self.#fields.append_by_ref(__i);)*
+                });
+            }
+        }
+        Data::Struct(_) => bail!(ident, "unit structs are not supported"),
         Data::Enum(data) => {
             if let Some(discriminant) = data.variants.iter().find_map(|v| v.discriminant.as_ref()) {
                 bail!(discriminant, "discriminants aren't supported");
@@ -88,15 +105,32 @@ pub fn arg(
         ..
     }: DeriveInput,
 ) -> Result {
-    let arg_type;
-    let signature;
-    match data {
-        Data::Enum(_) => {
-            arg_type = quote!(::dbus_client::__private::dbus::arg::ArgType::String);
-            signature = quote!("s".into())
+    let (arg_type, signature) = match data {
+        Data::Enum(_) => (
+            quote!(::dbus_client::__private::dbus::arg::ArgType::String),
+            quote!("s".into()),
+        ),
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(fields),
+            ..
+        }) => {
+            let fields = fields.unnamed.into_iter().map(|field| field.ty);
+            (
+                quote!(::dbus_client::__private::dbus::arg::ArgType::Struct),
+                quote! {
+                    let mut __s = String::from("(");
+                    #(__s.push_str(&<#fields as dbus::arg::Arg>::signature());)*
+                    __s.push(')');
+                    __s.into()
+                },
+            )
         }
-        _ => todo!("non enums"),
-    }
+        _ => bail!(
+            ident,
+            "only tuple structs and enums are supported";
+            help = "structs with named fields only support `Append`, e.g., as `a{{sv}}`"
+        ),
+    };
     ensure!(
         generics.to_token_stream().is_empty(),
         generics,
@@ -122,8 +156,7 @@ pub fn get(
         ..
     }: DeriveInput,
 ) -> Result {
-    let body;
-    match data {
+    let body = match data {
         Data::Enum(data) => {
             if let Some(discriminant) = data.variants.iter().find_map(|v| v.discriminant.as_ref()) {
                 bail!(discriminant, "discriminants aren't supported");
@@ -132,16 +165,37 @@ pub fn get(
                 let str = ident.to_string();
                 quote!(#str => Some(Self::#ident),)
             });
-            body = quote! {
+            quote! {
                 let __s: &str = __i.get()?;
                 match __s {
                     #(#variants)*
                     _ => None
                 }
-            };
+            }
         }
-        _ => todo!("non enums"),
-    }
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(fields),
+            ..
+        }) => {
+            let fields = fields.unnamed.into_iter().map(|field| {
+                let ty = field.ty;
+                quote! {{
+                    let __v: #ty = __s.get()?;
+                    __s.next();
+                    __v
+                }}
+            });
+            quote! {
+                let mut __s = __i.recurse(dbus::arg::ArgType::Struct)?;
+                Some(Self(#(#fields),*))
+            }
+        }
+        _ => bail!(
+            ident,
+            "only tuple structs and enums are supported";
+            help = "structs with named fields only support `Append`, e.g., as `a{{sv}}`"
+        ),
+    };
     ensure!(
         generics.to_token_stream().is_empty(),
         generics,
@@ -157,3 +211,33 @@ pub fn get(
         }
     })
 }
+
+#[test]
+fn unsupported_shapes() {
+    use manyhow::ToTokensError;
+    let error = |derive: fn(DeriveInput) -> Result, input: &str| {
+        let Err(error) = derive(syn::parse_str(input).unwrap()) else {
+            panic!("expected an error");
+        };
+        let message = error
+            .into_token_stream()
+            .into_iter()
+            .find_map(|token| match token {
+                proc_macro2::TokenTree::Group(group) => Some(group.stream()),
+                _ => None,
+            })
+            .unwrap();
+        syn::parse2::<LitStr>(message).unwrap().value()
+    };
+    for derive in [arg, get] {
+        assert_eq!(
+            error(derive, "struct Named { a: u32 }").trim_end(),
+            "only tuple structs and enums are supported\n\n  = help: structs with named fields \
+             only support `Append`, e.g., as `a{sv}`"
+        );
+        assert!(
+            error(derive, "struct Unit;").starts_with("only tuple structs and enums are supported")
+        );
+    }
+    assert!(error(append, "struct Unit;").starts_with("unit structs are not supported"));
+}
@@ -1,16 +1,22 @@
 use manyhow::{error_message, manyhow, Result, bail};
 use proc_macro2::TokenStream;
-use quote_use::{format_ident, quote_use as quote, ToTokens};
+use quote_use::{ToTokens, format_ident, quote_spanned_use as quote_spanned, quote_use as quote};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::token::{Brace, Paren};
-use syn::{braced, parenthesized, Ident, LitStr, Path, Token};
+use syn::{Ident, LitInt, LitStr, Path, Token, braced, parenthesized};
 
 mod dbus_object;
 
 // TODO partial parse
 #[manyhow(proc_macro)]
+pub use dbus_object::dbus_interface;
+#[manyhow(proc_macro)]
 pub use dbus_object::dbus_object;
+#[manyhow(proc_macro)]
+pub use dbus_object::dbus_object_from_xml;
+#[manyhow(proc_macro)]
+pub use dbus_object::dbus_type;
 
 mod derive;
 #[manyhow(proc_macro_derive(Append, attributes(value_signature)))]
@@ -0,0 +1,39 @@
+use dbus_client::dbus_object;
+use dbus_client::nonblock::DbusObject;
+
+dbus_object! {
+    Bus("org.freedesktop.DBus", "/org/freedesktop/DBus", async, timeout = 1s)
+    "org.freedesktop.DBus" {
+        GetId() -> s;
+        ListNames() -> a s;
+        Features: a s;
+        ~NameOwnerChanged(name: s, old_owner: s, new_owner: s);
+    }
+    "org.freedesktop.DBus.Peer" as peer {
+        Ping();
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), dbus_client::Error> {
+    let (resource, connection) =
+        dbus_tokio::connection::new_session_sync().map_err(dbus_client::Error::Transport)?;
+    // The resource drives the connection, it only returns if the connection is
+    // lost.
+    tokio::spawn(async {
+        let error = resource.await;
+        panic!("lost connection to D-Bus: {error}");
+    });
+
+    let bus = Bus::connect(connection, None);
+    bus.peer().ping().await?;
+    println!(
+        "Bus {} with features {:?}",
+        bus.get_id().await?,
+        bus.features().await?
+    );
+    for name in bus.list_names().await? {
+        println!("- {name}");
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use dbus_client::dbus_object;
+
+dbus_object! {
+    #[dbus(mock)]
+    Counter("org.example.Counter", "/org/example/Counter", session)
+    "org.example.Counter" {
+        /// Adds `amount` to the counter, returning the new value.
+        Add(amount: u) -> u;
+        Reset();
+        mut Step: u;
+        !Overflow;
+    }
+}
+
+/// Adds `amount`, starting over when the counter overflows.
+fn add_or_reset(
+    counter: &impl CounterApi,
+    amount: u32,
+) -> Result<u32, CounterOrgExampleCounterError> {
+    match counter.add(amount) {
+        Err(CounterOrgExampleCounterError::Overflow(_)) => {
+            counter.reset()?;
+            counter.add(amount)
+        }
+        result => result,
+    }
+}
+
+fn main() {
+    let mut counter = MockCounter::default();
+    counter
+        .expect_add()
+        .with((5,))
+        .times(1)
+        .return_error("org.example.Counter.Overflow", "counter overflowed");
+    counter.expect_reset().times(1).return_ok(());
+    counter.expect_add().returning(|(amount,)| Ok(amount));
+    counter.expect_step().return_ok(1);
+
+    assert_eq!(add_or_reset(&counter, 5).unwrap(), 5);
+    assert_eq!(counter.step().unwrap(), 1);
+    counter.checkpoint();
+    println!("ok");
+}
@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use dbus_client::{DbusObject, dbus_object_from_xml};
+
+dbus_object_from_xml!(
+    "examples/notifications.xml",
+    /// Desktop notifications as specified by the [Desktop Notifications Specification](https://specifications.freedesktop.org/notification-spec/latest/).
+    Notifications(
+        "org.freedesktop.Notifications",
+        "/org/freedesktop/Notifications",
+        session
+    )
+);
+
+type Result<T = (), E = dbus_client::Error> = std::result::Result<T, E>;
+
+fn main() -> Result {
+    let notifications = Notifications::session(Duration::from_secs(1))?;
+
+    dbg!(notifications.get_server_information()?);
+    dbg!(notifications.get_capabilities()?);
+
+    notifications.on_notification_closed(|signal, _, _| {
+        eprintln!("{} closed ({})", signal.id, signal.reason);
+        false
+    })?;
+
+    let id = notifications.notify(
+        "dbus-client".into(),
+        0,
+        String::new(),
+        "Hello".into(),
+        "from dbus-client".into(),
+        Vec::new(),
+        HashMap::new(),
+        -1,
+    )?;
+    notifications.close_notification(id)?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use dbus_client::{DbusObject, dbus_interface, dbus_object};
+
+dbus_interface! {
+    /// Implemented by every object on the bus.
+    Peer "org.freedesktop.DBus.Peer" {
+        /// Does nothing, useful to check whether the peer is alive.
+        Ping();
+        /// Returns the hex-encoded UUID of the machine the peer runs on.
+        GetMachineId() -> s;
+    }
+}
+
+dbus_object! {
+    Bus("org.freedesktop.DBus", "/org/freedesktop/DBus", session, timeout = 1s)
+    Peer;
+    "org.freedesktop.DBus" {
+        GetId() -> s;
+    }
+}
+
+fn main() -> Result<(), dbus_client::Error> {
+    let bus = Bus::session(None)?;
+    bus.ping()?;
+    println!("Bus {} on machine {}", bus.get_id()?, bus.get_machine_id()?);
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use std::thread;
+use std::time::Duration;
+
+use dbus::blocking::Connection;
+use dbus::channel::Sender;
+use dbus::message::SignalArgs;
+use dbus_client::server::{self, MethodErr};
+use dbus_client::{DbusObject, dbus_object};
+
+dbus_object! {
+    #[dbus(server)]
+    Counter("org.example.Counter", "/org/example/Counter", session, timeout = 1s)
+    "org.example.Counter" {
+        /// Adds `amount` to the counter, returning the new value.
+        Add(amount: u) -> u;
+        Reset();
+        mut Step: u;
+        ~Overflowed(value: u);
+    }
+}
+
+/// Test double of `Counter`.
+#[derive(Default)]
+struct FakeCounter {
+    value: u32,
+    step: u32,
+}
+
+impl CounterServer for FakeCounter {
+    fn add(&mut self, amount: u32) -> Result<u32, MethodErr> {
+        self.value = self
+            .value
+            .checked_add(amount * self.step.max(1))
+            .ok_or_else(|| MethodErr::failed("overflow"))?;
+        Ok(self.value)
+    }
+
+    fn reset(&mut self) -> Result<(), MethodErr> {
+        self.value = 0;
+        Ok(())
+    }
+
+    fn step(&self) -> Result<u32, MethodErr> {
+        Ok(self.step)
+    }
+
+    fn set_step(&mut self, value: u32) -> Result<(), MethodErr> {
+        self.step = value;
+        Ok(())
+    }
+}
+
+fn main() -> Result<(), dbus_client::Error> {
+    let service = Connection::new_session()?;
+    service.request_name("org.example.Counter", false, true, false)?;
+    server::register::<Counter, _>(&service, "/org/example/Counter", FakeCounter::default());
+    let signal = CounterOverflowed { value: u32::MAX };
+    service
+        .send(signal.to_emit_message(&"/org/example/Counter".into()))
+        .expect("signal is sent");
+    thread::spawn(move || {
+        loop {
+            service.process(Duration::from_secs(1)).unwrap();
+        }
+    });
+
+    let counter = Counter::session(None)?;
+    counter.set_step(2)?;
+    println!("step {}, value {}", counter.step()?, counter.add(3)?);
+    counter.reset()?;
+    println!("after reset {}", counter.add(1)?);
+    // Client and server are generated from the same definition.
+    assert!(DbusObject::verify(&counter)?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Shares objects between threads using an `Arc<SyncConnection>`.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use dbus::blocking::SyncConnection;
+use dbus_client::{DbusObject, dbus_object};
+
+dbus_object! {
+    Bus("org.freedesktop.DBus", "/org/freedesktop/DBus")
+    "org.freedesktop.DBus" {
+        GetId() -> s;
+        RequestName(name: s, flags: u) -> u;
+        ~NameAcquired(name: s);
+    }
+}
+
+dbus_object! {
+    Peer("org.freedesktop.DBus", "/org/freedesktop/DBus")
+    "org.freedesktop.DBus.Peer" {
+        GetMachineId() -> s;
+    }
+}
+
+type SharedBus = Bus<'static, Arc<SyncConnection>>;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let connection = Arc::new(SyncConnection::new_session()?);
+    let bus: SharedBus = Bus::connect(connection.clone(), None);
+
+    let threads: Vec<_> = (0..3)
+        .map(|_| {
+            let bus = bus.clone();
+            thread::spawn(move || bus.get_id())
+        })
+        .collect();
+    for thread in threads {
+        println!("Bus {}", thread.join().unwrap()?);
+    }
+
+    let peer: Peer<'static, _> = DbusObject::shared_sub_object(&bus, "/org/freedesktop/DBus");
+    drop(bus);
+    println!("Machine {}", peer.get_machine_id()?);
+
+    let bus: SharedBus = DbusObject::shared_sub_object(&peer, "/org/freedesktop/DBus");
+    bus.on_name_acquired(|acquired, _connection: &Arc<SyncConnection>, _| {
+        println!("Acquired {}", acquired.name);
+        false
+    })?;
+    bus.request_name("org.example.Shared".into(), 0)?;
+    while connection.process(Duration::from_millis(100))? {}
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Uses an in-memory transport instead of a bus connection, replying to calls
+//! and recording them.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use dbus::Message;
+use dbus_client::{DbusObject, Transport, dbus_object};
+
+dbus_object! {
+    Counter("org.example.Counter", "/org/example/Counter")
+    "org.example.Counter" {
+        Add(amount: u) -> u;
+        Reset();
+    }
+}
+
+/// Replies to `Add` with the sum of all amounts so far and records every
+/// message sent.
+#[derive(Default)]
+struct FakeCounter {
+    sum: Mutex<u32>,
+    sent: Mutex<Vec<String>>,
+}
+
+impl Transport for FakeCounter {
+    fn send_with_reply_and_block(
+        &self,
+        mut message: Message,
+        _timeout: Duration,
+    ) -> Result<Message, dbus::Error> {
+        self.sent.lock().unwrap().push(format!("{message:?}"));
+        // Replies require the serial a connection would assign when sending.
+        message.set_serial(1);
+        let mut sum = self.sum.lock().unwrap();
+        match message.member().as_deref().unwrap_or_default() {
+            "Add" => {
+                *sum += message.read1::<u32>()?;
+                Ok(message.method_return().append1(*sum))
+            }
+            "Reset" => {
+                *sum = 0;
+                Ok(message.method_return())
+            }
+            member => Err(dbus::Error::new_custom(
+                "org.freedesktop.DBus.Error.UnknownMethod",
+                &format!("unknown method `{member}`"),
+            )),
+        }
+    }
+
+    fn send(&self, message: Message) -> Result<(), dbus::Error> {
+        self.sent.lock().unwrap().push(format!("{message:?}"));
+        Ok(())
+    }
+}
+
+fn main() -> Result<(), dbus_client::Error> {
+    let transport = FakeCounter::default();
+    let counter = Counter::connect(&transport, None);
+    assert_eq!(counter.add(2)?, 2);
+    assert_eq!(counter.add(3)?, 5);
+    counter.reset()?;
+    assert_eq!(counter.add(1)?, 1);
+    for message in transport.sent.into_inner().unwrap() {
+        println!("{message}");
+    }
+    Ok(())
+}
@@ -1,7 +1,7 @@
 #![allow(non_snake_case, non_camel_case_types)]
 use std::time::Duration;
 
-use dbus_client::{dbus_object, Append, Arg, DbusObject, Get};
+use dbus_client::{Append, Arg, DbusObject, Get, dbus_object, shared};
 
 dbus_object! {
     /// Interface implemented by the main wpa_supplicant D-Bus object registered in the bus with fi.w1.wpa_supplicant1 name.
@@ -20,7 +20,7 @@ dbus_object! {
         ///
         /// # Arguments
         /// - `o` A D-Bus path to an object representing an interface
-        ///   to remove returned by [`CreateInterface`](WpaSupplicant::CreateInterface).
+        ///   to remove returned by [`create_interface`](WpaSupplicant::create_interface).
         ///
         /// # Errors
         /// - `fi.w1.wpa_supplicant1.InterfaceUnknown`
@@ -53,23 +53,30 @@ dbus_object! {
         Capabilities: a s;
         /// Wi-Fi Display subelements.
         mut WFDIEs: a y;
-        // TODO signals
-        // /// A new interface was added to wpa_supplicant.
-        // ///
-        // /// # Arguments
-        // /// - `interface` A D-Bus path to an object representing the added interface
-        // /// - `properties` A dictionary containing properties of added interface.
-        // ~InterfaceAdded(interface: @Interface, properties: a{s v});
-        // /// An interface was removed from wpa_supplicant.
-        // ///
-        // /// # Arguments
-        // /// - `interface` A D-Bus path to an object representing the removed interface.
-        // ~InterfaceRemoved(interface: @Interface)
-        // /// Some properties have changed.
-        // ///
-        // /// # Arguments
-        // /// - `properties` A dictionary with pairs of properties names which have changed and theirs new values. Possible dictionary keys are: "DebugParams"
-        // ~PropertiesChanged(properties: a{s v})
+        /// A new interface was added to wpa_supplicant.
+        ///
+        /// # Arguments
+        /// - `interface` A D-Bus path to an object representing the added interface
+        /// - `properties` A dictionary containing properties of added interface.
+        ~InterfaceAdded(interface: @Interface, properties: a{s v});
+        /// An interface was removed from wpa_supplicant.
+        ///
+        /// # Arguments
+        /// - `interface` A D-Bus path to an object representing the removed interface.
+        ~InterfaceRemoved(interface: @Interface);
+        /// Some properties have changed.
+        ///
+        /// # Arguments
+        /// - `properties` A dictionary with pairs of properties names which have changed and theirs new values. Possible dictionary keys are: "DebugParams"
+        ~PropertiesChanged(properties: a{s v});
+        /// `wpa_supplicant` already controls this interface.
+        !InterfaceExists;
+        /// Object pointed by the path doesn't exist or doesn't represent an interface.
+        !InterfaceUnknown;
+        /// Invalid entries were found in the passed argument.
+        !InvalidArgs;
+        /// The operation failed for an unknown reason.
+        !UnknownError;
     }
 }
 
@@ -96,7 +103,7 @@ enum DebugLevel {
 }
 
 dbus_object! {
-    /// Interface implemented by objects related to network interface added to `wpa_supplicant`, i.e., returned [`WpaSupplicant::CreateInterface`].
+    /// Interface implemented by objects related to network interface added to `wpa_supplicant`, i.e., returned [`WpaSupplicant::create_interface`].
     Interface "fi.w1.wpa_supplicant1.Interface" {
         /// Triggers a scan.
         ///
@@ -116,6 +123,15 @@ dbus_object! {
         /// - `fi.w1.wpa_supplicant1.UnknownError` Adding network failed for an unknown reason.
         AddNetwork(args: a{s v}) -> @Network;
         // TODO there is so much :o
+        /// Invalid entries were found in the passed argument.
+        #[dbus(name = "fi.w1.wpa_supplicant1.InvalidArgs")]
+        !InvalidArgs;
+        /// Interface is not connected to any network.
+        #[dbus(name = "fi.w1.wpa_supplicant1.NotConnected")]
+        !NotConnected;
+        /// The operation failed for an unknown reason.
+        #[dbus(name = "fi.w1.wpa_supplicant1.UnknownError")]
+        !UnknownError;
     }
 }
 
@@ -143,23 +159,38 @@ enum InterfaceScanType {
 
 dbus_object!(Network);
 
-type Result<T = (), E = dbus::Error> = std::result::Result<T, E>;
+type Result<T = (), E = dbus_client::Error> = std::result::Result<T, E>;
 
 fn main() -> Result {
     let wpa = WpaSupplicant::system(Duration::from_secs(1))?;
 
-    dbg!(wpa.get_DebugLevel()?);
+    wpa.on_interface_added(|signal, _, _| {
+        eprintln!("added {}", signal.interface);
+        true
+    })?;
+
+    dbg!(wpa.debug_level()?);
 
-    dbg!(wpa.CreateInterface(CreateInterface {
+    match wpa.create_interface(CreateInterface {
         Ifname: "name".into(),
         BridgeIfName: None,
         Driver: None,
         ConfigFile: None,
-    }))?;
+    }) {
+        Ok(interface) => eprintln!("created {interface:?}"),
+        Err(WpaSupplicantFiW1WpaSupplicant1Error::InterfaceExists(message)) => {
+            eprintln!("already exists: {message}")
+        }
+        Err(e @ WpaSupplicantFiW1WpaSupplicant1Error::Transport(_)) => return Err(e.into()),
+        Err(e) => eprintln!("{e}"),
+    }
 
-    for interface in wpa.get_Interfaces()? {
+    for interface in wpa.interfaces()? {
         eprintln!("{interface:?}")
     }
 
+    // Dispatches signals received in the meantime to `on_interface_added`.
+    while shared::system()?.process(Duration::ZERO)? {}
+
     Ok(())
 }
@@ -0,0 +1,174 @@
+use std::fmt::{self, Display};
+
+use dbus::arg::{ArgType, TypeMismatchError};
+
+/// Errors of calls to D-Bus objects.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The call did not reach the peer or no reply was received, e.g.,
+    /// because the connection was closed or the call timed out.
+    ///
+    /// libdbus reports these failures like error replies, so they are
+    /// recognized by their name: `org.freedesktop.DBus.Error.NoReply`,
+    /// `org.freedesktop.DBus.Error.Disconnected` and
+    /// `org.freedesktop.DBus.Error.NoMemory`, or no name at all. A peer
+    /// replying with one of these names is reported as `Transport` as well.
+    Transport(dbus::Error),
+    /// The peer replied with the error `name`.
+    Remote { name: String, message: String },
+    /// The reply to `member` of `interface` did not match the expected types.
+    ///
+    /// `expected` and `found` are the signatures of the reply, `position` is
+    /// the first argument that differs. When the signature matched but
+    /// decoding failed nonetheless, they are the D-Bus type codes of the
+    /// argument at `position`, e.g., `s` or `a`.
+    Decode {
+        interface: String,
+        member: String,
+        expected: String,
+        found: String,
+        position: u32,
+    },
+}
+
+impl Error {
+    /// The D-Bus name of the error, i.e., `org.freedesktop.DBus.Error.NoReply`
+    /// for timeouts.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Error::Transport(error) => error.name(),
+            Error::Remote { name, .. } => Some(name),
+            Error::Decode { .. } => None,
+        }
+    }
+}
+
+/// Reasons a reply could not be decoded, converted into [`Error::Decode`] once
+/// interface and member are known.
+pub(crate) enum DecodeError {
+    /// The signature of the reply did not match.
+    Signature { expected: String, found: String },
+    /// Decoding failed despite a matching signature.
+    Type(TypeMismatchError),
+}
+
+impl DecodeError {
+    pub(crate) fn into_error(self, interface: &str, member: &str) -> Error {
+        let (expected, found, position) = match self {
+            DecodeError::Signature { expected, found } => {
+                let position = complete_types(&expected)
+                    .zip(complete_types(&found))
+                    .take_while(|(expected, found)| expected == found)
+                    .count();
+                (expected, found, u32::try_from(position).unwrap_or(u32::MAX))
+            }
+            DecodeError::Type(error) => (
+                type_code(error.expected_arg_type()),
+                type_code(error.found_arg_type()),
+                error.pos(),
+            ),
+        };
+        Error::Decode {
+            interface: interface.to_owned(),
+            member: member.to_owned(),
+            expected,
+            found,
+            position,
+        }
+    }
+}
+
+/// Whether `error` was created locally by libdbus, e.g., on timeouts or when
+/// the connection was closed, instead of being sent by the peer.
+///
+/// Only names libdbus uses for failed calls are considered, names like
+/// `org.freedesktop.DBus.Error.Timeout` are used by services as well.
+fn is_transport_error(error: &dbus::Error) -> bool {
+    matches!(
+        error.name(),
+        None | Some(
+            "org.freedesktop.DBus.Error.NoReply"
+                | "org.freedesktop.DBus.Error.Disconnected"
+                | "org.freedesktop.DBus.Error.NoMemory"
+        )
+    )
+}
+
+/// Splits a signature into its complete types, i.e., `sa{sv}(ii)` into `s`,
+/// `a{sv}` and `(ii)`.
+fn complete_types(signature: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+    let mut start = 0;
+    signature.char_indices().filter_map(move |(idx, c)| {
+        match c {
+            '(' | '{' => depth += 1,
+            ')' | '}' => depth -= 1,
+            _ => {}
+        }
+        (depth == 0 && c != 'a').then(|| {
+            let ty = &signature[start..=idx];
+            start = idx + 1;
+            ty
+        })
+    })
+}
+
+fn type_code(arg_type: ArgType) -> String {
+    match arg_type {
+        ArgType::Invalid => String::new(),
+        arg_type => char::from(arg_type as u8).into(),
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Transport(error) => Display::fmt(error, f),
+            Error::Remote { name, message } => write!(f, "{name}: {message}"),
+            Error::Decode {
+                interface,
+                member,
+                expected,
+                found,
+                position,
+            } => write!(
+                f,
+                "invalid reply to `{interface}.{member}`: expected `{expected}`, found `{found}` \
+                 (argument {position})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(error) => Some(error),
+            Error::Remote { .. } | Error::Decode { .. } => None,
+        }
+    }
+}
+
+impl From<dbus::Error> for Error {
+    fn from(error: dbus::Error) -> Self {
+        if is_transport_error(&error) {
+            return Self::Transport(error);
+        }
+        Self::Remote {
+            name: error.name().unwrap_or_default().to_owned(),
+            message: error.message().unwrap_or_default().to_owned(),
+        }
+    }
+}
+
+impl From<Error> for dbus::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Transport(error) => error,
+            Error::Remote { name, message } => dbus::Error::new_custom(&name, &message),
+            error @ Error::Decode { .. } => dbus::Error::new_failed(&error.to_string()),
+        }
+    }
+}
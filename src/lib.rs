@@ -1,21 +1,142 @@
 #![warn(clippy::pedantic, /*missing_docs,*/ clippy::cargo)]
-#![allow(clippy::wildcard_imports)]
+#![allow(clippy::wildcard_imports, clippy::multiple_crate_versions)]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use __private::Result;
-use dbus::arg::{AppendAll, IterAppend, ReadAll};
-use dbus::blocking::{BlockingSender, Connection};
+use dbus::arg::{AppendAll, Arg, ArgType, Get, Iter, IterAppend, ReadAll};
+use dbus::blocking::SyncConnection;
+use dbus::channel::Token;
+use dbus::message::SignalArgs;
 use dbus::strings::{BusName, Interface, Member};
 use dbus::{Message, Path};
 pub use dbus_client_macros::*;
+use error::DecodeError;
+pub use error::Error;
+pub use transport::Transport;
+use verify::Declaration;
+#[cfg(feature = "verify")]
+use verify::Mismatch;
+
+mod error;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(feature = "nonblock")]
+pub mod nonblock;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod shared;
+pub mod transport;
+pub mod verify;
+
+/// Expands to its input if the `mock` feature is enabled, used for the mocks
+/// generated by `dbus_object!`.
+#[cfg(feature = "mock")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mock {
+    ($($tt:tt)*) => { $($tt)* };
+}
+
+#[cfg(not(feature = "mock"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mock {
+    ($($tt:tt)*) => {};
+}
 
 #[doc(hidden)]
 pub mod __private {
+    use std::marker::PhantomData;
+
     pub use dbus;
     use dbus::arg::{Append, Arg, IterAppend, Variant};
-    pub type Result<T, E = dbus::Error> = std::result::Result<T, E>;
+    pub type Result<T, E = crate::Error> = std::result::Result<T, E>;
+
+    /// Signature of `T`, if it implements [`Arg`], similar to [`DictValue`].
+    pub struct SignatureOf<T>(pub PhantomData<T>);
+
+    pub trait KnownSignature {
+        fn signature(&self) -> Option<String>;
+    }
+
+    pub trait UnknownSignature {
+        fn signature(&self) -> Option<String>;
+    }
+
+    impl<T: Arg> KnownSignature for SignatureOf<T> {
+        fn signature(&self) -> Option<String> {
+            Some(T::signature().to_string())
+        }
+    }
+
+    impl<T> UnknownSignature for &SignatureOf<T> {
+        fn signature(&self) -> Option<String> {
+            None
+        }
+    }
+
+    /// Concatenates `signatures`, `None` if any of them is unknown.
+    #[must_use]
+    pub fn concat_signatures(signatures: &[Option<String>]) -> Option<String> {
+        signatures
+            .iter()
+            .cloned()
+            .collect::<Option<Vec<_>>>()
+            .map(|s| s.concat())
+    }
+
+    /// Whether `functions` contains `function`, used to detect members of
+    /// `dbus_interface!`s conflicting with other interfaces at compile time.
+    #[must_use]
+    pub const fn contains(functions: &[&str], function: &str) -> bool {
+        let mut idx = 0;
+        while idx < functions.len() {
+            if str_eq(functions[idx], function) {
+                return true;
+            }
+            idx += 1;
+        }
+        false
+    }
+
+    /// Whether `a` and `b` share any function, see [`contains`].
+    #[must_use]
+    pub const fn intersect(a: &[&str], b: &[&str]) -> bool {
+        let mut idx = 0;
+        while idx < a.len() {
+            if contains(b, a[idx]) {
+                return true;
+            }
+            idx += 1;
+        }
+        false
+    }
+
+    #[test]
+    fn conflicts() {
+        assert!(contains(&["ping", "get_id"], "get_id"));
+        assert!(!contains(&["ping", "get_id"], "get"));
+        assert!(intersect(&["ping", "get_id"], &["get_id"]));
+        assert!(!intersect(&["ping"], &["get_id", "pong"]));
+    }
+
+    const fn str_eq(a: &str, b: &str) -> bool {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut idx = 0;
+        while idx < a.len() {
+            if a[idx] != b[idx] {
+                return false;
+            }
+            idx += 1;
+        }
+        true
+    }
 
     pub trait AppendToDict {
         fn append_to_dict(&self, key: &'static str, i: &mut IterAppend);
@@ -118,6 +239,81 @@ pub mod __private {
     }
 }
 
+/// Replies with a signature known at compile time, i.e., tuples of types
+/// implementing [`Arg`] and [`Get`].
+pub trait ReadReply: ReadAll {
+    /// The expected signature of the reply, e.g., `sa{sv}`.
+    fn signature() -> String;
+}
+
+impl ReadReply for () {
+    fn signature() -> String {
+        String::new()
+    }
+}
+
+macro_rules! read_reply {
+    ($($t:ident),+) => {
+        impl<$($t: Arg + for<'z> Get<'z>),+> ReadReply for ($($t,)+) {
+            fn signature() -> String {
+                [$(&*$t::signature()),+].concat()
+            }
+        }
+    };
+}
+
+read_reply!(A);
+read_reply!(A, B);
+read_reply!(A, B, C);
+read_reply!(A, B, C, D);
+read_reply!(A, B, C, D, E);
+read_reply!(A, B, C, D, E, F);
+read_reply!(A, B, C, D, E, F, G);
+read_reply!(A, B, C, D, E, F, G, H);
+read_reply!(A, B, C, D, E, F, G, H, I);
+read_reply!(A, B, C, D, E, F, G, H, I, J);
+read_reply!(A, B, C, D, E, F, G, H, I, J, K);
+read_reply!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// The signature of the remaining arguments of `iter`.
+fn signature(mut iter: Iter) -> String {
+    let mut signature = String::new();
+    while iter.arg_type() != ArgType::Invalid {
+        signature.push_str(&iter.signature());
+        iter.next();
+    }
+    signature
+}
+
+/// Reads `R` from `iter` after comparing their signatures.
+fn decode<R: ReadReply>(mut iter: Iter) -> std::result::Result<R, DecodeError> {
+    let expected = R::signature();
+    let found = signature(iter);
+    if expected != found {
+        return Err(DecodeError::Signature { expected, found });
+    }
+    R::read(&mut iter).map_err(DecodeError::Type)
+}
+
+/// Reads `T` from the variant in the reply of
+/// `org.freedesktop.DBus.Properties.Get` after comparing their signatures.
+fn decode_property<T: Arg + for<'z> Get<'z>>(
+    mut iter: Iter,
+) -> std::result::Result<T, DecodeError> {
+    let Some(mut value) = iter.recurse(ArgType::Variant) else {
+        return Err(DecodeError::Signature {
+            expected: "v".into(),
+            found: signature(iter),
+        });
+    };
+    let expected = T::signature().to_string();
+    let found = signature(value);
+    if expected != found {
+        return Err(DecodeError::Signature { expected, found });
+    }
+    value.read().map_err(DecodeError::Type)
+}
+
 pub trait CommonDestination {
     const DESTINATION: &'static str;
 }
@@ -134,6 +330,26 @@ pub enum MaybeOwned<'a, T> {
     Borrowed(&'a T),
 }
 
+impl<T: Clone> MaybeOwned<'_, T> {
+    /// Clones the value if it is borrowed.
+    #[must_use]
+    pub fn into_owned(self) -> MaybeOwned<'static, T> {
+        match self {
+            MaybeOwned::Owned(t) => MaybeOwned::Owned(t),
+            MaybeOwned::Borrowed(t) => MaybeOwned::Owned(t.clone()),
+        }
+    }
+}
+
+impl<T: Clone> Clone for MaybeOwned<'_, T> {
+    fn clone(&self) -> Self {
+        match self {
+            MaybeOwned::Owned(t) => MaybeOwned::Owned(t.clone()),
+            MaybeOwned::Borrowed(t) => MaybeOwned::Borrowed(t),
+        }
+    }
+}
+
 impl<T> AsRef<T> for MaybeOwned<'_, T> {
     fn as_ref(&self) -> &T {
         match self {
@@ -143,7 +359,9 @@ impl<T> AsRef<T> for MaybeOwned<'_, T> {
     }
 }
 
-impl<T> From<T> for MaybeOwned<'static, T> {
+// Bounded by `Transport`, so `Object::connect(&connection, None)` is not
+// ambiguous.
+impl<T: Transport> From<T> for MaybeOwned<'static, T> {
     fn from(value: T) -> Self {
         Self::Owned(value)
     }
@@ -156,14 +374,28 @@ impl<'a, T> From<&'a T> for MaybeOwned<'a, T> {
 }
 
 pub trait DbusObject<'a>: Sized {
+    /// Timeout used when constructing with `None`, set using `timeout = 5s` in
+    /// `dbus_object!`.
+    ///
+    /// Defaults to 25 seconds, the default of libdbus.
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(25);
+
+    /// The connection messages are sent through, i.e., the generic parameter
+    /// of the generated struct, e.g., `Bus<'_, SyncConnection>`, defaulting to
+    /// [`Connection`](dbus::blocking::Connection).
+    type Connection: Transport + 'a;
+
     fn new(
-        conn: impl Into<MaybeOwned<'a, Connection>>,
+        conn: impl Into<MaybeOwned<'a, Self::Connection>>,
         destination: impl Into<BusName<'a>>,
         path: impl Into<Path<'a>>,
-        timeout: Duration,
+        timeout: impl Into<Option<Duration>>,
     ) -> Self;
 
-    fn connect(conn: impl Into<MaybeOwned<'a, Connection>>, timeout: Duration) -> Self
+    fn connect(
+        conn: impl Into<MaybeOwned<'a, Self::Connection>>,
+        timeout: impl Into<Option<Duration>>,
+    ) -> Self
     where
         Self: CommonDestination + CommonPath,
     {
@@ -171,9 +403,9 @@ pub trait DbusObject<'a>: Sized {
     }
 
     fn with_destination(
-        conn: impl Into<MaybeOwned<'a, Connection>>,
+        conn: impl Into<MaybeOwned<'a, Self::Connection>>,
         destination: impl Into<BusName<'a>>,
-        timeout: Duration,
+        timeout: impl Into<Option<Duration>>,
     ) -> Self
     where
         Self: CommonPath,
@@ -182,9 +414,9 @@ pub trait DbusObject<'a>: Sized {
     }
 
     fn with_path(
-        conn: impl Into<MaybeOwned<'a, Connection>>,
+        conn: impl Into<MaybeOwned<'a, Self::Connection>>,
         path: impl Into<Path<'a>>,
-        timeout: Duration,
+        timeout: impl Into<Option<Duration>>,
     ) -> Self
     where
         Self: CommonDestination,
@@ -192,30 +424,72 @@ pub trait DbusObject<'a>: Sized {
         Self::new(conn, Self::DESTINATION, path, timeout)
     }
 
-    fn session(timeout: Duration) -> Result<Self>
+    /// Connects to the session bus, reusing the connection shared by all
+    /// objects, see [`shared::session`].
+    ///
+    /// # Errors
+    /// Fails if no connection to the session bus could be established.
+    fn session(timeout: impl Into<Option<Duration>>) -> Result<Self>
     where
-        Self: CommonDestination + CommonPath + CommonlySession,
+        Self: DbusObject<'a, Connection = Arc<SyncConnection>>
+            + CommonDestination
+            + CommonPath
+            + CommonlySession,
         'a: 'static,
     {
-        let conn = Connection::new_session()?;
+        let conn = shared::session().map_err(Error::Transport)?;
         Ok(Self::connect(conn, timeout))
     }
 
-    fn system(timeout: Duration) -> Result<Self>
+    /// Connects to the system bus, reusing the connection shared by all
+    /// objects, see [`shared::system`].
+    ///
+    /// # Errors
+    /// Fails if no connection to the system bus could be established.
+    fn system(timeout: impl Into<Option<Duration>>) -> Result<Self>
     where
-        Self: CommonDestination + CommonPath + CommonlySystem,
+        Self: DbusObject<'a, Connection = Arc<SyncConnection>>
+            + CommonDestination
+            + CommonPath
+            + CommonlySystem,
         'a: 'static,
     {
-        let conn = Connection::new_system()?;
+        let conn = shared::system().map_err(Error::Transport)?;
         Ok(Self::connect(conn, timeout))
     }
 
-    fn connection(this: &Self) -> &Connection;
+    /// Members declared in `dbus_object!`, compared with the remote object by
+    /// [`DbusObject::verify`].
+    #[must_use]
+    fn declarations() -> Vec<Declaration> {
+        Vec::new()
+    }
+
+    /// Compares the [declared members](DbusObject::declarations) with the
+    /// introspection data of the remote object, returning all differences.
+    ///
+    /// # Errors
+    /// Fails if the remote object could not be introspected.
+    #[cfg(feature = "verify")]
+    fn verify(this: &'a Self) -> Result<Vec<Mismatch>> {
+        let (xml,): (String,) = Self::method_call(
+            this,
+            "org.freedesktop.DBus.Introspectable",
+            "Introspect",
+            (),
+        )?;
+        Ok(verify::compare(&Self::declarations(), &xml))
+    }
+
+    fn connection(this: &Self) -> &Self::Connection;
     fn destination(this: &Self) -> &BusName<'a>;
     fn path(this: &Self) -> &Path<'a>;
     fn timeout(this: &Self) -> Duration;
 
-    fn sub_object<T: DbusObject<'a>>(this: &'a Self, path: impl Into<Path<'a>>) -> T {
+    fn sub_object<T: DbusObject<'a, Connection = Self::Connection>>(
+        this: &'a Self,
+        path: impl Into<Path<'a>>,
+    ) -> T {
         T::new(
             DbusObject::connection(this),
             DbusObject::destination(this),
@@ -224,20 +498,137 @@ pub trait DbusObject<'a>: Sized {
         )
     }
 
-    fn method_call<'b, R: ReadAll>(
+    /// Creates an object at `path` of the same destination, sharing the
+    /// connection instead of borrowing it, e.g., an `Arc<SyncConnection>`.
+    fn shared_sub_object<T: DbusObject<'static, Connection = Self::Connection>>(
+        this: &Self,
+        path: impl Into<Path<'static>>,
+    ) -> T
+    where
+        Self::Connection: Clone,
+    {
+        T::new(
+            DbusObject::connection(this).clone(),
+            DbusObject::destination(this).clone().into_static(),
+            path,
+            DbusObject::timeout(this),
+        )
+    }
+
+    /// Calls `member` on `interface` and waits for the reply.
+    ///
+    /// # Errors
+    /// Fails if the call fails, returning [`Error::Remote`] for errors sent by
+    /// the peer, or the signature of the reply does not match `R`, returning
+    /// [`Error::Decode`].
+    fn method_call<'b, R: ReadReply>(
         &'a self,
         interface: impl Into<Interface<'b>>,
         member: impl Into<Member<'b>>,
         args: impl AppendAll,
     ) -> Result<R> {
+        Self::method_call_with_timeout(self, interface, member, args, Self::timeout(self))
+    }
+
+    /// Calls `member` on `interface` and waits for the reply up to `timeout`
+    /// instead of [`DbusObject::timeout`].
+    ///
+    /// # Errors
+    /// Fails if the call fails or the signature of the reply does not match
+    /// `R`.
+    fn method_call_with_timeout<'b, R: ReadReply>(
+        &'a self,
+        interface: impl Into<Interface<'b>>,
+        member: impl Into<Member<'b>>,
+        args: impl AppendAll,
+        timeout: Duration,
+    ) -> Result<R> {
+        let interface = interface.into();
+        let member = member.into();
+        let mut msg = Message::method_call(
+            Self::destination(self),
+            Self::path(self),
+            &interface,
+            &member,
+        );
+        args.append(&mut IterAppend::new(&mut msg));
+        let r = Self::connection(self).send_with_reply_and_block(msg, timeout)?;
+        decode(r.iter_init()).map_err(|e| e.into_error(&interface, &member))
+    }
+
+    /// Reads `property` of `interface`.
+    ///
+    /// # Errors
+    /// Fails if the call fails or the signature of the value does not match
+    /// `T`.
+    fn property<'b, T: Arg + for<'z> Get<'z>>(
+        &'a self,
+        interface: impl Into<Interface<'b>>,
+        property: &str,
+    ) -> Result<T> {
+        Self::property_with_timeout(self, interface, property, Self::timeout(self))
+    }
+
+    /// Reads `property` of `interface`, waiting for the reply up to `timeout`
+    /// instead of [`DbusObject::timeout`].
+    ///
+    /// # Errors
+    /// Fails if the call fails or the signature of the value does not match
+    /// `T`.
+    fn property_with_timeout<'b, T: Arg + for<'z> Get<'z>>(
+        &'a self,
+        interface: impl Into<Interface<'b>>,
+        property: &str,
+        timeout: Duration,
+    ) -> Result<T> {
+        let interface = interface.into();
+        let mut msg = Message::method_call(
+            Self::destination(self),
+            Self::path(self),
+            &"org.freedesktop.DBus.Properties".into(),
+            &"Get".into(),
+        );
+        (&*interface, property).append(&mut IterAppend::new(&mut msg));
+        let r = Self::connection(self).send_with_reply_and_block(msg, timeout)?;
+        decode_property(r.iter_init()).map_err(|e| e.into_error(&interface, property))
+    }
+
+    /// Calls `member` on `interface` without waiting for a reply, i.e., with
+    /// the no-reply flag set.
+    ///
+    /// # Errors
+    /// Fails if the message could not be sent.
+    fn method_call_no_reply<'b>(
+        &'a self,
+        interface: impl Into<Interface<'b>>,
+        member: impl Into<Member<'b>>,
+        args: impl AppendAll,
+    ) -> Result<()> {
         let mut msg = Message::method_call(
             Self::destination(self),
             Self::path(self),
             &interface.into(),
             &member.into(),
         );
+        msg.set_no_reply(true);
         args.append(&mut IterAppend::new(&mut msg));
-        let r = Self::connection(self).send_with_reply_and_block(msg, Self::timeout(self))?;
-        Ok(R::read(&mut r.iter_init())?)
+        Self::connection(self).send(msg).map_err(Error::Transport)
+    }
+
+    /// Calls `f` for every signal `S` emitted by this object.
+    ///
+    /// `f` is only called while the connection is processed, e.g., using
+    /// [`Connection::process`](dbus::blocking::Connection::process).
+    /// Returning `false` from `f` removes the match.
+    ///
+    /// # Errors
+    /// Fails if the match rule could not be added to the bus.
+    fn match_signal<S, F>(&self, f: F) -> Result<Token>
+    where
+        S: SignalArgs + ReadAll,
+        F: FnMut(S, &Self::Connection, &Message) -> bool + Send + 'static,
+    {
+        let rule = S::match_rule(Some(Self::destination(self)), Some(Self::path(self)));
+        Ok(Self::connection(self).add_match(rule.static_clone(), f)?)
     }
 }
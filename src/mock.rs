@@ -0,0 +1,259 @@
+//! Mocks generated by `dbus_object!` with `#[dbus(mock)]`, implementing the
+//! same `{Name}Api` trait as the object, without a connection.
+//!
+//! ```ignore
+//! dbus_object! {
+//!     #[dbus(mock)]
+//!     Device("org.example.Device", "/org/example/Device")
+//!     "org.example.Device" {
+//!         Reset(mode: u) -> b;
+//!         State: s;
+//!     }
+//! }
+//!
+//! fn reset(device: &impl DeviceApi) -> bool { ... }
+//!
+//! let mut device = MockDevice::default();
+//! device.expect_reset().with((0,)).times(1).return_ok(true);
+//! device.expect_state().return_error("org.example.Error.Busy", "busy");
+//! assert!(reset(&device));
+//! ```
+//!
+//! Expectations are checked when the mock is dropped or using
+//! [`Mock::checkpoint`].
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use crate::Error;
+
+/// Expectations of a mock, keyed by the name of the function.
+#[derive(Default)]
+pub struct Mock {
+    expectations: Mutex<HashMap<&'static str, Vec<Box<dyn AnyExpectation>>>>,
+}
+
+impl Mock {
+    /// Adds an expectation for calls of `function` with the arguments `A`.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn expect<A: Send + 'static, R: Send + 'static, E: Send + 'static>(
+        &mut self,
+        function: &'static str,
+    ) -> &mut Expectation<A, R, E> {
+        let expectations = self
+            .expectations
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(function)
+            .or_default();
+        expectations.push(Box::new(Expectation::<A, R, E>::new(function)));
+        expectations
+            .last_mut()
+            .and_then(|e| e.as_any_mut().downcast_mut())
+            .expect("expectation was just added")
+    }
+
+    /// Calls the first expectation of `function` matching `args`, that was not
+    /// called as often as expected yet.
+    ///
+    /// # Errors
+    /// Returns the error of the matching expectation.
+    ///
+    /// # Panics
+    /// Panics if no expectation matches.
+    pub fn call<A: Send + 'static, R: Send + 'static, E: Send + 'static>(
+        &self,
+        function: &'static str,
+        args: A,
+    ) -> Result<R, E> {
+        let mut expectations = self
+            .expectations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let expectation = expectations
+            .get_mut(function)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.as_any_mut().downcast_mut::<Expectation<A, R, E>>())
+            .find(|e| !e.saturated() && e.matches(&args));
+        match expectation {
+            Some(expectation) => expectation.call(args),
+            None => panic!("unexpected call of `{function}`"),
+        }
+    }
+
+    /// Checks that all expectations were called as often as expected and
+    /// removes them.
+    ///
+    /// # Panics
+    /// Panics if any expectation was not satisfied.
+    pub fn checkpoint(&mut self) {
+        let expectations = std::mem::take(
+            self.expectations
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        let unsatisfied: Vec<_> = expectations
+            .values()
+            .flatten()
+            .filter_map(|e| e.unsatisfied())
+            .collect();
+        assert!(unsatisfied.is_empty(), "{}", unsatisfied.join("\n"));
+    }
+}
+
+impl Drop for Mock {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            self.checkpoint();
+        }
+    }
+}
+
+type Matcher<A> = Box<dyn Fn(&A) -> bool + Send>;
+type Returning<A, R, E> = Box<dyn FnMut(A) -> Result<R, E> + Send>;
+
+/// Expected calls of a function, taking the arguments `A` as tuple and
+/// returning `Result<R, E>`.
+pub struct Expectation<A, R, E> {
+    function: &'static str,
+    matcher: Option<Matcher<A>>,
+    returning: Option<Returning<A, R, E>>,
+    times: Option<usize>,
+    calls: usize,
+}
+
+impl<A: Send + 'static, R: Send + 'static, E: Send + 'static> Expectation<A, R, E> {
+    fn new(function: &'static str) -> Self {
+        Self {
+            function,
+            matcher: None,
+            returning: None,
+            times: None,
+            calls: 0,
+        }
+    }
+
+    /// Only matches calls with `args`.
+    pub fn with(&mut self, args: A) -> &mut Self
+    where
+        A: PartialEq,
+    {
+        self.withf(move |actual| *actual == args)
+    }
+
+    /// Only matches calls with arguments for which `matcher` returns `true`.
+    pub fn withf(&mut self, matcher: impl Fn(&A) -> bool + Send + 'static) -> &mut Self {
+        self.matcher = Some(Box::new(matcher));
+        self
+    }
+
+    /// Expects exactly `times` calls, further calls are matched by later
+    /// expectations.
+    pub fn times(&mut self, times: usize) -> &mut Self {
+        self.times = Some(times);
+        self
+    }
+
+    /// Computes the result of calls using `returning`, that is called with the
+    /// arguments.
+    pub fn returning(
+        &mut self,
+        returning: impl FnMut(A) -> Result<R, E> + Send + 'static,
+    ) -> &mut Self {
+        self.returning = Some(Box::new(returning));
+        self
+    }
+
+    /// Returns `value` on every call.
+    pub fn return_ok(&mut self, value: R) -> &mut Self
+    where
+        R: Clone,
+    {
+        self.returning(move |_| Ok(value.clone()))
+    }
+
+    /// Fails every call with the D-Bus error `name`, as if it was sent by the
+    /// peer, i.e., [`Error::Remote`].
+    pub fn return_error(&mut self, name: impl Into<String>, message: impl Into<String>) -> &mut Self
+    where
+        E: From<Error>,
+    {
+        let name = name.into();
+        let message = message.into();
+        self.returning(move |_| {
+            Err(Error::Remote {
+                name: name.clone(),
+                message: message.clone(),
+            }
+            .into())
+        })
+    }
+
+    fn saturated(&self) -> bool {
+        self.times.is_some_and(|times| self.calls >= times)
+    }
+
+    fn matches(&self, args: &A) -> bool {
+        self.matcher.as_ref().is_none_or(|matcher| matcher(args))
+    }
+
+    fn call(&mut self, args: A) -> Result<R, E> {
+        self.calls += 1;
+        let Some(returning) = &mut self.returning else {
+            panic!(
+                "no result for `{}`, set it using `returning` or `return_ok`",
+                self.function
+            )
+        };
+        returning(args)
+    }
+}
+
+/// Type erased [`Expectation`].
+trait AnyExpectation: Send {
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    /// Describes why the expectation is not satisfied.
+    fn unsatisfied(&self) -> Option<String>;
+}
+
+impl<A: Send + 'static, R: Send + 'static, E: Send + 'static> AnyExpectation
+    for Expectation<A, R, E>
+{
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn unsatisfied(&self) -> Option<String> {
+        self.times
+            .filter(|&times| times != self.calls)
+            .map(|times| {
+                format!(
+                    "`{}` was expected to be called {times} times, but was called {} times",
+                    self.function, self.calls
+                )
+            })
+    }
+}
+
+#[test]
+fn expectations() {
+    let mut mock = Mock::default();
+    mock.expect::<(u32,), u32, Error>("add")
+        .with((1,))
+        .times(1)
+        .return_error("org.example.Error.Overflow", "overflow");
+    mock.expect::<(u32,), u32, Error>("add")
+        .returning(|(amount,)| Ok(amount * 2));
+    let error = mock.call::<(u32,), u32, Error>("add", (1,)).unwrap_err();
+    assert_eq!(error.name(), Some("org.example.Error.Overflow"));
+    assert_eq!(mock.call::<(u32,), u32, Error>("add", (1,)).unwrap(), 2);
+    assert_eq!(mock.call::<(u32,), u32, Error>("add", (3,)).unwrap(), 6);
+    mock.checkpoint();
+
+    mock.expect::<(), (), Error>("reset").times(2).return_ok(());
+    mock.call::<_, (), Error>("reset", ()).unwrap();
+    let unsatisfied = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| mock.checkpoint()));
+    assert!(unsatisfied.is_err());
+}
@@ -0,0 +1,262 @@
+//! Asynchronous objects, generated by `dbus_object!` with `async` in its
+//! header, e.g., `Bus("org.freedesktop.DBus", "/org/freedesktop/DBus", async)`.
+//!
+//! They are built on [`dbus::nonblock`], the connection needs to be driven by
+//! an executor, e.g., using [`dbus-tokio`](https://docs.rs/dbus-tokio).
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use dbus::arg::{AppendAll, Arg, Get, Iter, IterAppend, ReadAll, TypeMismatchError};
+use dbus::channel::Sender;
+use dbus::message::SignalArgs;
+use dbus::nonblock::{MsgMatch, Proxy, SyncConnection};
+use dbus::strings::{BusName, Interface, Member};
+use dbus::{Message, Path};
+
+use crate::__private::Result;
+use crate::verify::Declaration;
+#[cfg(feature = "verify")]
+use crate::verify::{self, Mismatch};
+use crate::{
+    CommonDestination, CommonPath, DecodeError, Error, ReadReply, decode, decode_property,
+};
+
+/// Reply decoded by [`Proxy::method_call`], keeping the reason decoding failed.
+struct Decoded<R>(std::result::Result<R, DecodeError>);
+
+impl<R: ReadReply> ReadAll for Decoded<R> {
+    fn read(iter: &mut Iter) -> std::result::Result<Self, TypeMismatchError> {
+        Ok(Self(decode(*iter)))
+    }
+}
+
+/// Reply of `org.freedesktop.DBus.Properties.Get`, see [`Decoded`].
+struct DecodedProperty<T>(std::result::Result<T, DecodeError>);
+
+impl<T: Arg + for<'z> Get<'z>> ReadAll for DecodedProperty<T> {
+    fn read(iter: &mut Iter) -> std::result::Result<Self, TypeMismatchError> {
+        Ok(Self(decode_property(*iter)))
+    }
+}
+
+/// Asynchronous version of [`crate::DbusObject`].
+pub trait DbusObject<'a>: Sized {
+    /// Timeout used when constructing with `None`, set using `timeout = 5s` in
+    /// `dbus_object!`.
+    ///
+    /// Defaults to 25 seconds, the default of libdbus.
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(25);
+
+    fn new(
+        conn: Arc<SyncConnection>,
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+        timeout: impl Into<Option<Duration>>,
+    ) -> Self;
+
+    fn connect(conn: Arc<SyncConnection>, timeout: impl Into<Option<Duration>>) -> Self
+    where
+        Self: CommonDestination + CommonPath,
+    {
+        Self::new(conn, Self::DESTINATION, Self::PATH, timeout)
+    }
+
+    fn with_destination(
+        conn: Arc<SyncConnection>,
+        destination: impl Into<BusName<'a>>,
+        timeout: impl Into<Option<Duration>>,
+    ) -> Self
+    where
+        Self: CommonPath,
+    {
+        Self::new(conn, destination, Self::PATH, timeout)
+    }
+
+    fn with_path(
+        conn: Arc<SyncConnection>,
+        path: impl Into<Path<'a>>,
+        timeout: impl Into<Option<Duration>>,
+    ) -> Self
+    where
+        Self: CommonDestination,
+    {
+        Self::new(conn, Self::DESTINATION, path, timeout)
+    }
+
+    /// Members declared in `dbus_object!`, see
+    /// [`crate::DbusObject::declarations`].
+    #[must_use]
+    fn declarations() -> Vec<Declaration> {
+        Vec::new()
+    }
+
+    /// Compares the [declared members](DbusObject::declarations) with the
+    /// introspection data of the remote object, see
+    /// [`crate::DbusObject::verify`].
+    ///
+    /// # Errors
+    /// Fails if the remote object could not be introspected.
+    #[cfg(feature = "verify")]
+    fn verify(&self) -> impl Future<Output = Result<Vec<Mismatch>>> + Send + 'static {
+        let reply = Self::method_call(
+            self,
+            "org.freedesktop.DBus.Introspectable",
+            "Introspect",
+            (),
+        );
+        async move {
+            let (xml,): (String,) = reply.await?;
+            Ok(verify::compare(&Self::declarations(), &xml))
+        }
+    }
+
+    fn connection(this: &Self) -> &Arc<SyncConnection>;
+    fn destination(this: &Self) -> &BusName<'a>;
+    fn path(this: &Self) -> &Path<'a>;
+    fn timeout(this: &Self) -> Duration;
+
+    fn sub_object<T: DbusObject<'a>>(this: &Self, path: impl Into<Path<'a>>) -> T {
+        T::new(
+            Self::connection(this).clone(),
+            Self::destination(this).clone(),
+            path,
+            Self::timeout(this),
+        )
+    }
+
+    /// Calls `member` on `interface` and waits for the reply.
+    ///
+    /// # Errors
+    /// Fails if the call fails or the signature of the reply does not match
+    /// `R`, see [`crate::DbusObject::method_call`].
+    fn method_call<'b, R: ReadReply + 'static>(
+        &self,
+        interface: impl Into<Interface<'b>>,
+        member: impl Into<Member<'b>>,
+        args: impl AppendAll,
+    ) -> impl Future<Output = Result<R>> + Send + 'static {
+        Self::method_call_with_timeout(self, interface, member, args, Self::timeout(self))
+    }
+
+    /// Calls `member` on `interface` and waits for the reply up to `timeout`
+    /// instead of [`DbusObject::timeout`].
+    ///
+    /// # Errors
+    /// Fails if the call fails or the signature of the reply does not match
+    /// `R`.
+    fn method_call_with_timeout<'b, R: ReadReply + 'static>(
+        &self,
+        interface: impl Into<Interface<'b>>,
+        member: impl Into<Member<'b>>,
+        args: impl AppendAll,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<R>> + Send + 'static {
+        let interface = interface.into().into_static();
+        let member = member.into().into_static();
+        let proxy = Proxy::new(
+            Self::destination(self),
+            Self::path(self),
+            timeout,
+            Self::connection(self).clone(),
+        );
+        let reply = proxy.method_call(interface.clone(), member.clone(), args);
+        async move {
+            let Decoded(reply) = reply.await?;
+            reply.map_err(|e| e.into_error(&interface, &member))
+        }
+    }
+
+    /// Reads `property` of `interface`.
+    ///
+    /// # Errors
+    /// Fails if the call fails or the signature of the value does not match
+    /// `T`.
+    fn property<'b, T: Arg + for<'z> Get<'z> + 'static>(
+        &self,
+        interface: impl Into<Interface<'b>>,
+        property: &str,
+    ) -> impl Future<Output = Result<T>> + Send + 'static {
+        Self::property_with_timeout(self, interface, property, Self::timeout(self))
+    }
+
+    /// Reads `property` of `interface`, waiting for the reply up to `timeout`
+    /// instead of [`DbusObject::timeout`].
+    ///
+    /// # Errors
+    /// Fails if the call fails or the signature of the value does not match
+    /// `T`.
+    fn property_with_timeout<'b, T: Arg + for<'z> Get<'z> + 'static>(
+        &self,
+        interface: impl Into<Interface<'b>>,
+        property: &str,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<T>> + Send + 'static {
+        let interface = interface.into().into_static();
+        let property = property.to_owned();
+        let proxy = Proxy::new(
+            Self::destination(self),
+            Self::path(self),
+            timeout,
+            Self::connection(self).clone(),
+        );
+        let reply = proxy.method_call(
+            "org.freedesktop.DBus.Properties",
+            "Get",
+            (&*interface, &*property),
+        );
+        async move {
+            let DecodedProperty(reply) = reply.await?;
+            reply.map_err(|e| e.into_error(&interface, &property))
+        }
+    }
+
+    /// Calls `member` on `interface` without waiting for a reply, i.e., with
+    /// the no-reply flag set.
+    ///
+    /// # Errors
+    /// Fails if the message could not be sent.
+    fn method_call_no_reply<'b>(
+        &self,
+        interface: impl Into<Interface<'b>>,
+        member: impl Into<Member<'b>>,
+        args: impl AppendAll,
+    ) -> Result<()> {
+        let mut msg = Message::method_call(
+            Self::destination(self),
+            Self::path(self),
+            &interface.into(),
+            &member.into(),
+        );
+        msg.set_no_reply(true);
+        args.append(&mut IterAppend::new(&mut msg));
+        Self::connection(self).send(msg).map_err(|()| {
+            Error::Transport(dbus::Error::new_failed("sending the message failed"))
+        })?;
+        Ok(())
+    }
+
+    /// Calls `f` for every signal `S` emitted by this object, until `f`
+    /// returns `false` or the match is removed using
+    /// [`SyncConnection::remove_match`].
+    ///
+    /// # Errors
+    /// Fails if the match rule could not be added to the bus.
+    fn match_signal<S, F>(
+        &self,
+        mut f: F,
+    ) -> impl Future<Output = Result<MsgMatch>> + Send + 'static
+    where
+        S: SignalArgs + ReadAll + 'static,
+        F: FnMut(S, &Message) -> bool + Send + 'static,
+    {
+        let rule = S::match_rule(Some(Self::destination(self)), Some(Self::path(self)));
+        let rule = rule.static_clone();
+        let connection = Self::connection(self).clone();
+        async move {
+            let signal = connection.add_match(rule).await?;
+            Ok(signal.cb(move |message: Message, args: S| f(args, &message)))
+        }
+    }
+}
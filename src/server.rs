@@ -0,0 +1,140 @@
+//! Serving objects implementing the server traits generated by `dbus_object!`
+//! with `#[dbus(server)]`, built on [`dbus_crossroads`].
+//!
+//! ```ignore
+//! dbus_object! {
+//!     #[dbus(server)]
+//!     Device("org.example.Device", "/org/example/Device")
+//!     "org.example.Device" {
+//!         Reset(mode: u) -> b;
+//!     }
+//! }
+//!
+//! struct Fake;
+//!
+//! impl DeviceServer for Fake {
+//!     fn reset(&mut self, mode: u32) -> Result<bool, MethodErr> {
+//!         Ok(mode == 0)
+//!     }
+//! }
+//!
+//! let _token = server::register::<Device, _>(&connection, "/org/example/Device", Fake);
+//! ```
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use dbus::Path;
+use dbus::blocking::{Connection, LocalConnection, SyncConnection};
+use dbus::channel::{MatchingReceiver, Token};
+use dbus::message::MatchRule;
+pub use dbus_crossroads::{Crossroads, IfaceBuilder, IfaceToken, MethodErr};
+
+use crate::Error;
+
+/// Connections objects can be served on, implemented for [`Connection`],
+/// [`SyncConnection`], [`LocalConnection`] and [`Arc`]s of them.
+pub trait ServerConnection {
+    /// Handles method calls received on this connection using `crossroads`,
+    /// see [`serve`].
+    fn serve(&self, crossroads: Crossroads) -> Token;
+}
+
+impl ServerConnection for Connection {
+    fn serve(&self, mut crossroads: Crossroads) -> Token {
+        self.start_receive(
+            MatchRule::new_method_call(),
+            Box::new(move |message, connection| {
+                // Only fails for messages that are no method calls.
+                let _ = crossroads.handle_message(message, connection);
+                true
+            }),
+        )
+    }
+}
+
+impl ServerConnection for LocalConnection {
+    fn serve(&self, mut crossroads: Crossroads) -> Token {
+        self.start_receive(
+            MatchRule::new_method_call(),
+            Box::new(move |message, connection| {
+                let _ = crossroads.handle_message(message, connection);
+                true
+            }),
+        )
+    }
+}
+
+impl ServerConnection for SyncConnection {
+    fn serve(&self, crossroads: Crossroads) -> Token {
+        // Handlers of a `SyncConnection` need to be `Sync`.
+        let crossroads = Mutex::new(crossroads);
+        self.start_receive(
+            MatchRule::new_method_call(),
+            Box::new(move |message, connection| {
+                let _ = crossroads
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .handle_message(message, connection);
+                true
+            }),
+        )
+    }
+}
+
+impl<T: ServerConnection + ?Sized> ServerConnection for Arc<T> {
+    fn serve(&self, crossroads: Crossroads) -> Token {
+        T::serve(self, crossroads)
+    }
+}
+
+/// Replies with the error of a call, e.g., when forwarding calls to another
+/// object, see [`dbus::Error::from`](#impl-From<Error>-for-Error).
+impl From<Error> for MethodErr {
+    fn from(error: Error) -> Self {
+        dbus::Error::from(error).into()
+    }
+}
+
+/// Interfaces of a `dbus_object!` with `#[dbus(server)]`, implemented by
+/// every `T` implementing its server trait.
+pub trait Interfaces<T: Send + 'static> {
+    /// Registers the interfaces in `crossroads`, calling the server trait of
+    /// `T`.
+    fn register(crossroads: &mut Crossroads) -> Vec<IfaceToken<T>>;
+}
+
+/// Inserts `implementation` at `path` into `crossroads`, implementing the
+/// interfaces of the object `O`.
+///
+/// Inserting multiple objects of the same type registers their interfaces
+/// each time, use [`Interfaces::register`] and [`Crossroads::insert`] to
+/// reuse them.
+pub fn insert<O: Interfaces<T>, T: Send + 'static>(
+    crossroads: &mut Crossroads,
+    path: impl Into<Path<'static>>,
+    implementation: T,
+) {
+    let interfaces = O::register(crossroads);
+    crossroads.insert(path, &interfaces, implementation);
+}
+
+/// Handles method calls received on `connection` using `crossroads`, while the
+/// connection is processed, e.g., using [`Connection::process`].
+///
+/// Returns the token to stop using
+/// [`MatchingReceiver::stop_receive`].
+pub fn serve(connection: &impl ServerConnection, crossroads: Crossroads) -> Token {
+    connection.serve(crossroads)
+}
+
+/// Serves `implementation` at `path` on `connection`, implementing the
+/// interfaces of the object `O`, see [`serve`].
+pub fn register<O: Interfaces<T>, T: Send + 'static>(
+    connection: &impl ServerConnection,
+    path: impl Into<Path<'static>>,
+    implementation: T,
+) -> Token {
+    let mut crossroads = Crossroads::new();
+    insert::<O, T>(&mut crossroads, path, implementation);
+    serve(connection, crossroads)
+}
@@ -0,0 +1,104 @@
+//! Connections shared by all objects created using
+//! [`DbusObject::session`](crate::DbusObject::session) and
+//! [`DbusObject::system`](crate::DbusObject::system).
+//!
+//! They are established on first use and kept for the lifetime of the process,
+//! unless replaced, e.g., after the bus restarted:
+//!
+//! ```ignore
+//! let bus = Bus::session(None)?;
+//! // `bus` keeps using the previous connection.
+//! shared::replace_session(None);
+//! let bus = Bus::session(None)?;
+//! ```
+//!
+//! Signals of these objects are only dispatched while the connection is
+//! processed, e.g., using `shared::session()?.process(timeout)`, which must
+//! only be done by one thread at a time.
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use dbus::blocking::SyncConnection;
+use dbus::channel::{BusType, Channel};
+
+static SESSION: Mutex<Option<Arc<SyncConnection>>> = Mutex::new(None);
+static SYSTEM: Mutex<Option<Arc<SyncConnection>>> = Mutex::new(None);
+
+fn get<T: Clone>(
+    shared: &Mutex<Option<T>>,
+    connect: impl FnOnce() -> Result<T, dbus::Error>,
+) -> Result<T, dbus::Error> {
+    let mut shared = shared.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(connection) = &*shared {
+        return Ok(connection.clone());
+    }
+    let connection = connect()?;
+    *shared = Some(connection.clone());
+    Ok(connection)
+}
+
+fn replace<T>(shared: &Mutex<Option<T>>, connection: Option<T>) -> Option<T> {
+    std::mem::replace(
+        &mut *shared.lock().unwrap_or_else(PoisonError::into_inner),
+        connection,
+    )
+}
+
+fn connect(bus: BusType) -> Result<Arc<SyncConnection>, dbus::Error> {
+    Ok(Arc::new(SyncConnection::from(Channel::get_private(bus)?)))
+}
+
+/// The shared connection to the session bus, connecting on first use.
+///
+/// # Errors
+/// Fails if no connection to the session bus could be established.
+pub fn session() -> Result<Arc<SyncConnection>, dbus::Error> {
+    get(&SESSION, || connect(BusType::Session))
+}
+
+/// The shared connection to the system bus, connecting on first use.
+///
+/// # Errors
+/// Fails if no connection to the system bus could be established.
+pub fn system() -> Result<Arc<SyncConnection>, dbus::Error> {
+    get(&SYSTEM, || connect(BusType::System))
+}
+
+/// Replaces the shared connection to the session bus, returning the previous
+/// one. With `None`, the next use connects again.
+///
+/// Objects already created keep using the previous connection.
+#[allow(clippy::must_use_candidate)]
+pub fn replace_session(connection: Option<Arc<SyncConnection>>) -> Option<Arc<SyncConnection>> {
+    replace(&SESSION, connection)
+}
+
+/// Replaces the shared connection to the system bus, returning the previous
+/// one. With `None`, the next use connects again.
+///
+/// Objects already created keep using the previous connection.
+#[allow(clippy::must_use_candidate)]
+pub fn replace_system(connection: Option<Arc<SyncConnection>>) -> Option<Arc<SyncConnection>> {
+    replace(&SYSTEM, connection)
+}
+
+#[test]
+fn replace_connections() {
+    let shared = Mutex::new(None);
+    let mut connections = 0;
+    let mut connect = || {
+        connections += 1;
+        Ok(Arc::new(connections))
+    };
+    let first = get(&shared, &mut connect).unwrap();
+    assert!(Arc::ptr_eq(&first, &get(&shared, &mut connect).unwrap()));
+
+    let replaced = replace(&shared, Some(Arc::new(10))).unwrap();
+    assert!(Arc::ptr_eq(&first, &replaced));
+    assert_eq!(*get(&shared, &mut connect).unwrap(), 10);
+
+    // Resetting connects again on next use.
+    assert_eq!(replace(&shared, None).as_deref(), Some(&10));
+    assert_eq!(*get(&shared, &mut connect).unwrap(), 2);
+    assert_eq!(connections, 2);
+}
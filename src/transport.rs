@@ -0,0 +1,141 @@
+//! Connections objects send their messages through, see
+//! [`DbusObject::Connection`](crate::DbusObject::Connection).
+//!
+//! Implemented for the blocking connections of [`dbus`], other implementations
+//! can, e.g., record messages or reply to them in memory:
+//!
+//! ```ignore
+//! struct Recorder(Connection, Mutex<Vec<Message>>);
+//!
+//! impl Transport for Recorder { ... }
+//!
+//! let bus = Bus::connect(Recorder(Connection::new_session()?, Mutex::default()), None);
+//! ```
+//!
+//! Objects using an `Arc<SyncConnection>` share their connection, they are
+//! `Send + Sync + Clone` and can be `'static`, see
+//! [`DbusObject::shared_sub_object`](crate::DbusObject::shared_sub_object).
+
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use dbus::Message;
+use dbus::arg::ReadAll;
+use dbus::blocking::{BlockingSender, Connection, LocalConnection, SyncConnection};
+use dbus::channel::Token;
+use dbus::message::MatchRule;
+
+/// Sends messages of a [`DbusObject`](crate::DbusObject) and dispatches
+/// signals to it.
+pub trait Transport {
+    /// Sends `message` and waits for the reply up to `timeout`.
+    ///
+    /// # Errors
+    /// Fails if no reply was received or the reply is an error.
+    fn send_with_reply_and_block(
+        &self,
+        message: Message,
+        timeout: Duration,
+    ) -> Result<Message, dbus::Error>;
+
+    /// Sends `message` without waiting for a reply.
+    ///
+    /// # Errors
+    /// Fails if the message could not be sent.
+    fn send(&self, message: Message) -> Result<(), dbus::Error>;
+
+    /// Calls `f` for every message matching `rule`, until `f` returns `false`.
+    ///
+    /// # Errors
+    /// Fails if the match rule could not be added, by default, i.e., for
+    /// transports without signals.
+    fn add_match<S, F>(&self, rule: MatchRule<'static>, f: F) -> Result<Token, dbus::Error>
+    where
+        S: ReadAll,
+        F: FnMut(S, &Self, &Message) -> bool + Send + 'static,
+    {
+        let _ = (rule, f);
+        Err(dbus::Error::new_failed(
+            "signals are not supported by this transport",
+        ))
+    }
+}
+
+macro_rules! blocking_transport {
+    ($connection:ty) => {
+        impl Transport for $connection {
+            fn send_with_reply_and_block(
+                &self,
+                message: Message,
+                timeout: Duration,
+            ) -> Result<Message, dbus::Error> {
+                BlockingSender::send_with_reply_and_block(self, message, timeout)
+            }
+
+            fn send(&self, message: Message) -> Result<(), dbus::Error> {
+                let channel = self.channel();
+                // Only fails if the message could not be queued.
+                channel.send(message).map_err(|()| {
+                    dbus::Error::new_custom(
+                        "org.freedesktop.DBus.Error.NoMemory",
+                        "not enough memory to queue the message",
+                    )
+                })?;
+                // Messages sent on closed connections are dropped silently.
+                channel.flush();
+                if channel.is_connected() {
+                    Ok(())
+                } else {
+                    Err(dbus::Error::new_custom(
+                        "org.freedesktop.DBus.Error.Disconnected",
+                        "the connection is closed",
+                    ))
+                }
+            }
+
+            fn add_match<S, F>(&self, rule: MatchRule<'static>, f: F) -> Result<Token, dbus::Error>
+            where
+                S: ReadAll,
+                F: FnMut(S, &Self, &Message) -> bool + Send + 'static,
+            {
+                // `SyncConnection` requires `Sync` callbacks.
+                let f = Mutex::new(f);
+                self.add_match(rule, move |args, connection: &Self, message: &Message| {
+                    f.lock().unwrap_or_else(PoisonError::into_inner)(args, connection, message)
+                })
+            }
+        }
+    };
+}
+
+blocking_transport!(Connection);
+blocking_transport!(SyncConnection);
+blocking_transport!(LocalConnection);
+
+/// Shares the connection `T`, passing the `Arc` to signal handlers.
+impl<T: Transport + Send + Sync + 'static> Transport for Arc<T> {
+    fn send_with_reply_and_block(
+        &self,
+        message: Message,
+        timeout: Duration,
+    ) -> Result<Message, dbus::Error> {
+        T::send_with_reply_and_block(self, message, timeout)
+    }
+
+    fn send(&self, message: Message) -> Result<(), dbus::Error> {
+        T::send(self, message)
+    }
+
+    fn add_match<S, F>(&self, rule: MatchRule<'static>, mut f: F) -> Result<Token, dbus::Error>
+    where
+        S: ReadAll,
+        F: FnMut(S, &Self, &Message) -> bool + Send + 'static,
+    {
+        // A strong reference would keep the connection alive through its own
+        // match.
+        let this = Arc::downgrade(self);
+        T::add_match(self, rule, move |args, _: &T, message: &Message| {
+            this.upgrade().is_some_and(|this| f(args, &this, message))
+        })
+    }
+}
@@ -0,0 +1,398 @@
+//! Comparison of generated objects with the introspection data of the remote
+//! object, see [`DbusObject::verify`](crate::DbusObject::verify).
+//!
+//! The [declarations](crate::DbusObject::declarations) are always available,
+//! the comparison requires the `verify` feature.
+
+use std::fmt::{self, Display};
+
+#[cfg(feature = "verify")]
+use dbus_client_codegen::introspect::{self, Node};
+
+/// A member declared in `dbus_object!` or `dbus_interface!`.
+///
+/// Signatures are `None` when they contain types not implementing
+/// [`Arg`](dbus::arg::Arg), e.g., structs deriving only
+/// [`Append`](crate::Append), those are not compared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Declaration {
+    Method {
+        interface: &'static str,
+        name: &'static str,
+        args: Option<String>,
+        output: Option<String>,
+    },
+    Property {
+        interface: &'static str,
+        name: &'static str,
+        ty: Option<String>,
+        access: Access,
+    },
+    Signal {
+        interface: &'static str,
+        name: &'static str,
+        args: Option<String>,
+    },
+}
+
+impl Declaration {
+    /// The interface declaring this member.
+    #[must_use]
+    pub fn interface(&self) -> &'static str {
+        match self {
+            Declaration::Method { interface, .. }
+            | Declaration::Property { interface, .. }
+            | Declaration::Signal { interface, .. } => interface,
+        }
+    }
+}
+
+/// Access of a declared property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl Access {
+    /// Whether the property can be read.
+    #[must_use]
+    pub fn readable(self) -> bool {
+        matches!(self, Access::Read | Access::ReadWrite)
+    }
+
+    /// Whether the property can be written.
+    #[must_use]
+    pub fn writable(self) -> bool {
+        matches!(self, Access::Write | Access::ReadWrite)
+    }
+
+    /// The value of the `access` attribute in introspection data, i.e.,
+    /// `read`, `write` or `readwrite`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Access::Read => "read",
+            Access::Write => "write",
+            Access::ReadWrite => "readwrite",
+        }
+    }
+}
+
+#[cfg(feature = "verify")]
+impl From<introspect::Access> for Access {
+    fn from(access: introspect::Access) -> Self {
+        match access {
+            introspect::Access::Read => Access::Read,
+            introspect::Access::Write => Access::Write,
+            introspect::Access::ReadWrite => Access::ReadWrite,
+        }
+    }
+}
+
+/// Kind of a member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberKind {
+    Method,
+    Property,
+    Signal,
+}
+
+impl Display for MemberKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MemberKind::Method => "method",
+            MemberKind::Property => "property",
+            MemberKind::Signal => "signal",
+        })
+    }
+}
+
+/// A difference between a [`Declaration`] and the remote object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Mismatch {
+    /// The remote object returned invalid introspection data.
+    InvalidIntrospection(String),
+    /// The remote object does not implement `interface`.
+    MissingInterface { interface: String },
+    /// The remote object does not have `member`.
+    MissingMember {
+        interface: String,
+        member: String,
+        kind: MemberKind,
+    },
+    /// The arguments of a method or signal differ.
+    Arguments {
+        interface: String,
+        member: String,
+        kind: MemberKind,
+        expected: String,
+        found: String,
+    },
+    /// The output of a method differs.
+    Output {
+        interface: String,
+        method: String,
+        expected: String,
+        found: String,
+    },
+    /// The type of a property differs.
+    Type {
+        interface: String,
+        property: String,
+        expected: String,
+        found: String,
+    },
+    /// The property does not support the declared access, e.g., is declared
+    /// `mut` but read-only.
+    Access {
+        interface: String,
+        property: String,
+        expected: Access,
+        found: Access,
+    },
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::InvalidIntrospection(error) => write!(f, "{error}"),
+            Mismatch::MissingInterface { interface } => {
+                write!(f, "interface `{interface}` is not implemented")
+            }
+            Mismatch::MissingMember {
+                interface,
+                member,
+                kind,
+            } => write!(f, "{kind} `{interface}.{member}` does not exist"),
+            Mismatch::Arguments {
+                interface,
+                member,
+                kind,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{kind} `{interface}.{member}` takes `{found}` instead of `{expected}`"
+            ),
+            Mismatch::Output {
+                interface,
+                method,
+                expected,
+                found,
+            } => write!(
+                f,
+                "method `{interface}.{method}` returns `{found}` instead of `{expected}`"
+            ),
+            Mismatch::Type {
+                interface,
+                property,
+                expected,
+                found,
+            } => write!(
+                f,
+                "property `{interface}.{property}` is `{found}` instead of `{expected}`"
+            ),
+            Mismatch::Access {
+                interface,
+                property,
+                expected,
+                found,
+            } => write!(
+                f,
+                "property `{interface}.{property}` is `{}` instead of `{}`",
+                found.as_str(),
+                expected.as_str()
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "verify")]
+fn signature(args: &[introspect::Arg]) -> String {
+    args.iter().map(|arg| arg.ty.signature()).collect()
+}
+
+/// Compares `declarations` with the introspection `xml` of the remote object.
+#[cfg(feature = "verify")]
+pub(crate) fn compare(declarations: &[Declaration], xml: &str) -> Vec<Mismatch> {
+    let node = match Node::from_xml(xml) {
+        Ok(node) => node,
+        Err(e) => return vec![Mismatch::InvalidIntrospection(e.to_string())],
+    };
+    let mut mismatches = Vec::new();
+    for declaration in declarations {
+        let name = declaration.interface();
+        if let Some(interface) = node.interfaces.iter().find(|i| i.name == name) {
+            compare_member(declaration, interface, &mut mismatches);
+        } else {
+            let missing = Mismatch::MissingInterface {
+                interface: name.to_owned(),
+            };
+            if !mismatches.contains(&missing) {
+                mismatches.push(missing);
+            }
+        }
+    }
+    mismatches
+}
+
+#[cfg(feature = "verify")]
+fn compare_member(
+    declaration: &Declaration,
+    interface: &introspect::Interface,
+    mismatches: &mut Vec<Mismatch>,
+) {
+    let interface_name = || interface.name.clone();
+    let differs = |expected: &Option<String>, found: &str| {
+        expected
+            .as_deref()
+            .filter(|expected| *expected != found)
+            .map(ToOwned::to_owned)
+    };
+    let missing = |member: &str, kind| Mismatch::MissingMember {
+        interface: interface_name(),
+        member: member.to_owned(),
+        kind,
+    };
+    match declaration {
+        Declaration::Method {
+            name, args, output, ..
+        } => {
+            let Some(method) = interface.members.iter().find_map(|m| match m {
+                introspect::Member::Method(m) if m.name == *name => Some(m),
+                _ => None,
+            }) else {
+                mismatches.push(missing(name, MemberKind::Method));
+                return;
+            };
+            let found = signature(&method.args);
+            if let Some(expected) = differs(args, &found) {
+                mismatches.push(Mismatch::Arguments {
+                    interface: interface_name(),
+                    member: (*name).to_owned(),
+                    kind: MemberKind::Method,
+                    expected,
+                    found,
+                });
+            }
+            let found = signature(&method.outputs);
+            if let Some(expected) = differs(output, &found) {
+                mismatches.push(Mismatch::Output {
+                    interface: interface_name(),
+                    method: (*name).to_owned(),
+                    expected,
+                    found,
+                });
+            }
+        }
+        Declaration::Property {
+            name, ty, access, ..
+        } => {
+            let Some(property) = interface.members.iter().find_map(|m| match m {
+                introspect::Member::Property(p) if p.name == *name => Some(p),
+                _ => None,
+            }) else {
+                mismatches.push(missing(name, MemberKind::Property));
+                return;
+            };
+            let found = property.ty.signature();
+            if let Some(expected) = differs(ty, &found) {
+                mismatches.push(Mismatch::Type {
+                    interface: interface_name(),
+                    property: (*name).to_owned(),
+                    expected,
+                    found,
+                });
+            }
+            if access.readable() && !property.access.readable()
+                || access.writable() && !property.access.writable()
+            {
+                mismatches.push(Mismatch::Access {
+                    interface: interface_name(),
+                    property: (*name).to_owned(),
+                    expected: *access,
+                    found: property.access.into(),
+                });
+            }
+        }
+        Declaration::Signal { name, args, .. } => {
+            let Some(signal) = interface.members.iter().find_map(|m| match m {
+                introspect::Member::Signal(s) if s.name == *name => Some(s),
+                _ => None,
+            }) else {
+                mismatches.push(missing(name, MemberKind::Signal));
+                return;
+            };
+            let found = signature(&signal.args);
+            if let Some(expected) = differs(args, &found) {
+                mismatches.push(Mismatch::Arguments {
+                    interface: interface_name(),
+                    member: (*name).to_owned(),
+                    kind: MemberKind::Signal,
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(feature = "verify")]
+#[test]
+fn compare_declarations() {
+    let xml = r#"<node>
+      <interface name="org.example.Device">
+        <method name="Reset">
+          <arg type="u" direction="in"/>
+          <arg type="b" direction="out"/>
+        </method>
+        <property name="State" type="s" access="read"/>
+        <signal name="Changed">
+          <arg type="a{sv}"/>
+        </signal>
+      </interface>
+    </node>"#;
+    let declarations = [
+        Declaration::Method {
+            interface: "org.example.Device",
+            name: "Reset",
+            args: Some("u".into()),
+            output: Some("s".into()),
+        },
+        Declaration::Property {
+            interface: "org.example.Device",
+            name: "State",
+            ty: None,
+            access: Access::ReadWrite,
+        },
+        Declaration::Signal {
+            interface: "org.example.Device",
+            name: "Changed",
+            args: Some("a{sv}".into()),
+        },
+        Declaration::Signal {
+            interface: "org.example.Device",
+            name: "Removed",
+            args: Some(String::new()),
+        },
+        Declaration::Property {
+            interface: "org.example.Other",
+            name: "State",
+            ty: Some("s".into()),
+            access: Access::Read,
+        },
+    ];
+    let mismatches: Vec<_> = compare(&declarations, xml)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(mismatches, [
+        "method `org.example.Device.Reset` returns `b` instead of `s`",
+        "property `org.example.Device.State` is `read` instead of `readwrite`",
+        "signal `org.example.Device.Removed` does not exist",
+        "interface `org.example.Other` is not implemented",
+    ]);
+}
@@ -0,0 +1,69 @@
+//! An in-memory [`Transport`] replying to calls and recording them.
+#![allow(dead_code)]
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use dbus::Message;
+use dbus_client::Transport;
+
+type Handler = dyn Fn(&Message) -> Result<Message, dbus::Error> + Send + Sync;
+
+/// A message sent through [`FakeBus`].
+pub struct Sent {
+    pub message: Message,
+    /// `None` for messages sent without waiting for a reply.
+    pub timeout: Option<Duration>,
+}
+
+/// Replies to calls using a handler and records every message sent.
+pub struct FakeBus {
+    handler: Box<Handler>,
+    sent: Mutex<Vec<Sent>>,
+}
+
+impl FakeBus {
+    pub fn new(
+        handler: impl Fn(&Message) -> Result<Message, dbus::Error> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            handler: Box::new(handler),
+            sent: Mutex::default(),
+        }
+    }
+
+    /// Takes the messages sent so far.
+    pub fn take(&self) -> Vec<Sent> {
+        std::mem::take(&mut self.sent.lock().unwrap())
+    }
+}
+
+impl Transport for FakeBus {
+    fn send_with_reply_and_block(
+        &self,
+        mut message: Message,
+        timeout: Duration,
+    ) -> Result<Message, dbus::Error> {
+        // Replies require the serial a connection would assign when sending.
+        message.set_serial(1);
+        let reply = (self.handler)(&message);
+        self.sent.lock().unwrap().push(Sent {
+            message,
+            timeout: Some(timeout),
+        });
+        reply
+    }
+
+    fn send(&self, message: Message) -> Result<(), dbus::Error> {
+        self.sent.lock().unwrap().push(Sent {
+            message,
+            timeout: None,
+        });
+        Ok(())
+    }
+}
+
+/// The member called by `message`, e.g., `Get` for properties.
+pub fn member(message: &Message) -> String {
+    message.member().map(|m| m.to_string()).unwrap_or_default()
+}
@@ -0,0 +1,88 @@
+use dbus_client::verify::{Access, Declaration};
+use dbus_client::{Append, DbusObject, dbus_object};
+
+#[derive(Append)]
+pub struct Options {
+    pub force: bool,
+}
+
+dbus_object! {
+    Device("org.example", "/org/example/Device")
+    "org.example.Device" {
+        Reset(mode: u) -> b;
+        Configure(options: Options);
+        mut State: s;
+        ~Changed(properties: a{s v});
+    }
+    "org.example.Device.Legacy" as legacy {
+        GetVersion() -> (u u);
+    }
+}
+
+#[test]
+fn declarations() {
+    assert_eq!(<Device>::declarations(), [
+        Declaration::Method {
+            interface: "org.example.Device",
+            name: "Reset",
+            args: Some("u".into()),
+            output: Some("b".into()),
+        },
+        Declaration::Method {
+            interface: "org.example.Device",
+            name: "Configure",
+            args: None,
+            output: Some(String::new()),
+        },
+        Declaration::Property {
+            interface: "org.example.Device",
+            name: "State",
+            ty: Some("s".into()),
+            access: Access::ReadWrite,
+        },
+        Declaration::Signal {
+            interface: "org.example.Device",
+            name: "Changed",
+            args: Some("a{sv}".into()),
+        },
+        Declaration::Method {
+            interface: "org.example.Device.Legacy",
+            name: "GetVersion",
+            args: Some(String::new()),
+            output: Some("uu".into()),
+        },
+    ]);
+}
+
+#[cfg(feature = "nonblock")]
+mod nonblock {
+    use dbus_client::dbus_object;
+    use dbus_client::nonblock::DbusObject;
+    use dbus_client::verify::{Access, Declaration};
+
+    dbus_object! {
+        Device("org.example", "/org/example/Device", async)
+        "org.example.Device" {
+            Reset(mode: u) -> b;
+            State: s;
+        }
+    }
+
+    #[test]
+    fn declarations() {
+        assert_eq!(Device::declarations(), [
+            Declaration::Method {
+                interface: "org.example.Device",
+                name: "Reset",
+                args: Some("u".into()),
+                output: Some("b".into()),
+            },
+            Declaration::Property {
+                interface: "org.example.Device",
+                name: "State",
+                ty: Some("s".into()),
+                access: Access::Read,
+            },
+        ]);
+    }
+}
@@ -0,0 +1,111 @@
+use dbus_client::{DbusObject, Error, dbus_object};
+
+mod common;
+use common::FakeBus;
+
+dbus_object! {
+    Manager("org.example.Manager", "/org/example/Manager")
+    "org.example.Manager" {
+        Create(name: s) -> u;
+        /// The name is already taken.
+        !Exists;
+        #[dbus(name = "org.example.Error.InvalidArgs")]
+        !InvalidArgs;
+    }
+    "org.example.Legacy" as legacy {
+        Create(name: s) -> u;
+    }
+}
+
+/// Replies to `Create(name)` depending on `name`.
+fn manager() -> FakeBus {
+    FakeBus::new(|call| {
+        let reply = call.method_return();
+        let error = |name, message| Err(dbus::Error::new_custom(name, message));
+        match call.read1().unwrap() {
+            "ok" => Ok(reply.append1(1u32)),
+            "exists" => error("org.example.Manager.Exists", "`exists` exists"),
+            "invalid" => error("org.example.Error.InvalidArgs", "invalid name"),
+            "unknown" => error("org.example.Error.Unknown", "what happened"),
+            "timeout" => error("org.freedesktop.DBus.Error.NoReply", "no reply"),
+            "remote timeout" => error("org.freedesktop.DBus.Error.Timeout", "gave up"),
+            "wrong" => Ok(reply.append2(1u32, "two")),
+            "mismatch" => Ok(reply.append1("one")),
+            name => panic!("unexpected name `{name}`"),
+        }
+    })
+}
+
+#[test]
+fn error_enums() {
+    let bus = manager();
+    let manager = Manager::connect(&bus, None);
+    let create = |name: &str| manager.create(name.into());
+    type ManagerError = ManagerOrgExampleManagerError;
+
+    assert_eq!(create("ok").unwrap(), 1);
+    assert!(
+        matches!(create("exists"), Err(ManagerError::Exists(message)) if message == "`exists` exists")
+    );
+    assert!(
+        matches!(create("invalid"), Err(ManagerError::InvalidArgs(message)) if message == "invalid name")
+    );
+    assert!(matches!(
+        create("unknown"),
+        Err(ManagerError::Unknown { name, message })
+            if name == "org.example.Error.Unknown" && message == "what happened"
+    ));
+    assert!(matches!(create("timeout"), Err(ManagerError::Transport(_))));
+    assert!(matches!(create("wrong"), Err(ManagerError::Decode { .. })));
+
+    let error = create("exists").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "org.example.Manager.Exists: `exists` exists"
+    );
+    assert!(matches!(
+        Error::from(error),
+        Error::Remote { name, .. } if name == "org.example.Manager.Exists"
+    ));
+}
+
+#[test]
+fn errors() {
+    let bus = manager();
+    let manager = Manager::connect(&bus, None);
+    let create = |name: &str| manager.legacy().create(name.into());
+
+    assert!(matches!(
+        create("exists"),
+        Err(Error::Remote { name, message })
+            if name == "org.example.Manager.Exists" && message == "`exists` exists"
+    ));
+    let error = create("timeout").unwrap_err();
+    assert!(matches!(error, Error::Transport(_)));
+    assert_eq!(error.name(), Some("org.freedesktop.DBus.Error.NoReply"));
+    // Services may reply with generic errors, they are not mistaken for local
+    // failures.
+    assert!(matches!(
+        create("remote timeout"),
+        Err(Error::Remote { name, .. }) if name == "org.freedesktop.DBus.Error.Timeout"
+    ));
+
+    let error = create("wrong").unwrap_err();
+    assert!(matches!(
+        &error,
+        Error::Decode { interface, member, expected, found, position }
+            if interface == "org.example.Legacy"
+                && member == "Create"
+                && expected == "u"
+                && found == "us"
+                && *position == 1
+    ));
+    assert_eq!(
+        error.to_string(),
+        "invalid reply to `org.example.Legacy.Create`: expected `u`, found `us` (argument 1)"
+    );
+    assert!(matches!(
+        create("mismatch"),
+        Err(Error::Decode { expected, found, position: 0, .. }) if expected == "u" && found == "s"
+    ));
+}
@@ -0,0 +1,98 @@
+use dbus::arg::Variant;
+use dbus_client::verify::Declaration;
+use dbus_client::{DbusObject, dbus_interface, dbus_object};
+
+mod common;
+use common::{FakeBus, member};
+
+dbus_interface! {
+    Peer "org.freedesktop.DBus.Peer" {
+        GetMachineId() -> s;
+        Ping();
+    }
+}
+
+dbus_object! {
+    Bus("org.freedesktop.DBus", "/org/freedesktop/DBus")
+    Peer;
+    "org.freedesktop.DBus" {
+        GetId() -> s;
+    }
+}
+
+dbus_object! {
+    Other("org.example.Other", "/org/example/Other")
+    Peer;
+}
+
+fn peer() -> FakeBus {
+    FakeBus::new(|call| {
+        let reply = call.method_return();
+        Ok(match member(call).as_str() {
+            "GetMachineId" => reply.append1(call.path().unwrap().to_string()),
+            "GetId" => reply.append1("id"),
+            "Ping" => reply,
+            member => panic!("unexpected call of `{member}`"),
+        })
+    })
+}
+
+#[test]
+fn named_interfaces() {
+    let bus = peer();
+    let object = Bus::connect(&bus, None);
+    assert_eq!(object.get_machine_id().unwrap(), "/org/freedesktop/DBus");
+    assert_eq!(object.get_id().unwrap(), "id");
+    let other = Other::connect(&bus, None);
+    assert_eq!(other.get_machine_id().unwrap(), "/org/example/Other");
+    other.ping().unwrap();
+
+    let interfaces: Vec<_> = bus
+        .take()
+        .into_iter()
+        .map(|sent| sent.message.interface().unwrap().to_string())
+        .collect();
+    assert_eq!(interfaces, [
+        "org.freedesktop.DBus.Peer",
+        "org.freedesktop.DBus",
+        "org.freedesktop.DBus.Peer",
+        "org.freedesktop.DBus.Peer",
+    ]);
+
+    assert!(
+        <Other>::declarations()
+            .iter()
+            .any(|d| matches!(d, Declaration::Method {
+                interface: "org.freedesktop.DBus.Peer",
+                name: "Ping",
+                ..
+            }))
+    );
+    assert_eq!(<Other as Peer>::__FUNCTIONS, ["get_machine_id", "ping"]);
+}
+
+dbus_object! {
+    Device("org.example", "/org/example/Device")
+    "org.example.Device" {
+        State: u;
+    }
+    "org.example.Device.Legacy" as legacy {
+        State: s;
+    }
+}
+
+#[test]
+fn views() {
+    let bus = FakeBus::new(|call| {
+        let (interface, _): (String, String) = call.read2().unwrap();
+        Ok(match interface.as_str() {
+            "org.example.Device" => call.method_return().append1(Variant(1u32)),
+            _ => call.method_return().append1(Variant("legacy")),
+        })
+    });
+    let device = Device::connect(&bus, None);
+    assert_eq!(device.state().unwrap(), 1);
+    assert_eq!(device.interface_org_example_Device().state().unwrap(), 1);
+    let legacy: DeviceLegacy<FakeBus> = device.legacy();
+    assert_eq!(legacy.state().unwrap(), "legacy");
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use dbus::arg::Variant;
+use dbus_client::{DbusObject, dbus_object};
+
+mod common;
+use common::{FakeBus, member};
+
+dbus_object! {
+    Player("org.example.Player", "/org/example/Player")
+    "org.example.Player" {
+        #[dbus(no_reply)]
+        Next();
+        #[dbus(no_reply)]
+        Seek(offset: x);
+    }
+}
+
+fn player() -> FakeBus {
+    FakeBus::new(|call| panic!("unexpected call waiting for a reply: {call:?}"))
+}
+
+#[test]
+fn no_reply() {
+    let bus = player();
+    let player = Player::connect(&bus, None);
+    player.next().unwrap();
+    player.seek(-5).unwrap();
+    DbusObject::method_call_no_reply(&player, "org.example.Player", "Next", ()).unwrap();
+
+    let sent = bus.take();
+    assert_eq!(sent.len(), 3);
+    for sent in &sent {
+        assert!(sent.message.get_no_reply());
+        assert_eq!(sent.timeout, None);
+    }
+    assert_eq!(sent[1].message.read1::<i64>().unwrap(), -5);
+}
+
+dbus_object! {
+    Supplicant("fi.w1.wpa_supplicant1", "/fi/w1/wpa_supplicant1", timeout = 5s)
+    "fi.w1.wpa_supplicant1" {
+        GetInterface(name: s) -> o;
+        #[dbus(timeout = 2min)]
+        Scan();
+        #[dbus(timeout = 500ms)]
+        State: s;
+        DebugLevel: s;
+    }
+}
+
+fn timeouts(supplicant: Supplicant<'_, FakeBus>) -> Vec<Option<Duration>> {
+    supplicant.get_interface("wlan0".into()).unwrap();
+    supplicant.scan().unwrap();
+    supplicant.state().unwrap();
+    supplicant.debug_level().unwrap();
+    let bus: &FakeBus = DbusObject::connection(&supplicant);
+    bus.take().into_iter().map(|sent| sent.timeout).collect()
+}
+
+#[test]
+fn timeouts_override_object() {
+    let bus = FakeBus::new(|call| {
+        let reply = call.method_return();
+        Ok(match member(call).as_str() {
+            "GetInterface" => {
+                reply.append1(dbus::Path::from("/fi/w1/wpa_supplicant1/Interfaces/0"))
+            }
+            "Scan" => reply,
+            "Get" => reply.append1(Variant("")),
+            member => panic!("unexpected call of `{member}`"),
+        })
+    });
+
+    assert_eq!(<Supplicant>::DEFAULT_TIMEOUT, Duration::from_secs(5));
+    assert_eq!(timeouts(Supplicant::connect(&bus, None)), [
+        Some(Duration::from_secs(5)),
+        Some(Duration::from_secs(120)),
+        Some(Duration::from_millis(500)),
+        Some(Duration::from_secs(5)),
+    ]);
+    assert_eq!(
+        timeouts(Supplicant::connect(&bus, Duration::from_secs(1))),
+        [
+            Some(Duration::from_secs(1)),
+            Some(Duration::from_secs(120)),
+            Some(Duration::from_millis(500)),
+            Some(Duration::from_secs(1)),
+        ]
+    );
+}
@@ -0,0 +1,86 @@
+use dbus::arg::Variant;
+use dbus_client::{DbusObject, dbus_object};
+
+mod common;
+use common::{FakeBus, member};
+
+dbus_object! {
+    Wpa("fi.w1.wpa_supplicant1", "/fi/w1/wpa_supplicant1")
+    "fi.w1.wpa_supplicant1" {
+        CreateInterface(name: s) -> s;
+        mut DebugLevel: s;
+        #[dbus(name = "WFDIEs")]
+        mut wfd_ies: a y;
+    }
+}
+
+dbus_object! {
+    #[dbus(legacy_naming)]
+    LegacyWpa("fi.w1.wpa_supplicant1", "/fi/w1/wpa_supplicant1")
+    "fi.w1.wpa_supplicant1" {
+        CreateInterface(name: s) -> s;
+        mut DebugLevel: s;
+    }
+}
+
+fn wpa() -> FakeBus {
+    FakeBus::new(|call| {
+        let reply = call.method_return();
+        Ok(match member(call).as_str() {
+            "CreateInterface" => reply.append1(call.read1::<&str>().unwrap()),
+            "Get" => match call.read2::<&str, &str>().unwrap().1 {
+                "DebugLevel" => reply.append1(Variant("debug")),
+                "WFDIEs" => reply.append1(Variant(vec![1u8])),
+                property => panic!("unexpected property `{property}`"),
+            },
+            "Set" => reply,
+            member => panic!("unexpected call of `{member}`"),
+        })
+    })
+}
+
+/// Members and properties called or set, in order.
+fn called(bus: &FakeBus) -> Vec<String> {
+    bus.take()
+        .into_iter()
+        .map(|sent| match member(&sent.message).as_str() {
+            "Get" | "Set" => {
+                let (_, property): (&str, &str) = sent.message.read2().unwrap();
+                format!("{} {property}", member(&sent.message))
+            }
+            member => member.to_owned(),
+        })
+        .collect()
+}
+
+#[test]
+fn rust_naming() {
+    let bus = wpa();
+    let wpa = Wpa::connect(&bus, None);
+    assert_eq!(wpa.create_interface("wlan0".into()).unwrap(), "wlan0");
+    assert_eq!(wpa.debug_level().unwrap(), "debug");
+    wpa.set_debug_level("info".into()).unwrap();
+    assert_eq!(wpa.wfd_ies().unwrap(), [1]);
+    wpa.set_wfd_ies(vec![2]).unwrap();
+    assert_eq!(called(&bus), [
+        "CreateInterface",
+        "Get DebugLevel",
+        "Set DebugLevel",
+        "Get WFDIEs",
+        "Set WFDIEs",
+    ]);
+}
+
+#[test]
+fn legacy_naming() {
+    let bus = wpa();
+    let wpa = LegacyWpa::connect(&bus, None);
+    assert_eq!(wpa.CreateInterface("wlan0".into()).unwrap(), "wlan0");
+    assert_eq!(wpa.get_DebugLevel().unwrap(), "debug");
+    wpa.set_DebugLevel("info".into()).unwrap();
+    assert_eq!(called(&bus), [
+        "CreateInterface",
+        "Get DebugLevel",
+        "Set DebugLevel"
+    ]);
+}
@@ -0,0 +1,46 @@
+use dbus::arg::Variant;
+use dbus_client::verify::{Access, Declaration};
+use dbus_client::{DbusObject, dbus_object};
+
+mod common;
+use common::{FakeBus, member};
+
+dbus_object! {
+    Account("org.example.Account", "/org/example/Account")
+    "org.example.Account" {
+        Name: s;
+        mut Email: s;
+        write Password: s;
+    }
+}
+
+#[test]
+fn write_only() {
+    let bus = FakeBus::new(|call| {
+        assert_eq!(member(call), "Set");
+        Ok(call.method_return())
+    });
+    let account = Account::connect(&bus, None);
+    account.set_password("secret".into()).unwrap();
+
+    let sent = bus.take();
+    let (interface, property, Variant(value)): (&str, &str, Variant<&str>) =
+        sent[0].message.read3().unwrap();
+    assert_eq!(
+        (interface, property, value),
+        ("org.example.Account", "Password", "secret")
+    );
+
+    let access: Vec<_> = <Account>::declarations()
+        .into_iter()
+        .map(|declaration| match declaration {
+            Declaration::Property { name, access, .. } => (name, access),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(access, [
+        ("Name", Access::Read),
+        ("Email", Access::ReadWrite),
+        ("Password", Access::Write),
+    ]);
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "server")]
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use dbus::Message;
+use dbus::blocking::{LocalConnection, SyncConnection};
+use dbus::channel::Sender;
+use dbus_client::dbus_object;
+use dbus_client::server::{self, Crossroads, MethodErr};
+
+mod common;
+use common::FakeBus;
+
+dbus_object! {
+    #[dbus(server)]
+    Manager("org.example.Manager", "/org/example/Manager")
+    "org.example.Manager" {
+        Create(name: s) -> u;
+        !Exists;
+    }
+}
+
+#[derive(Default)]
+struct Names(Vec<String>);
+
+impl Names {
+    fn check(&self, name: &str) -> Result<(), ManagerOrgExampleManagerError> {
+        if self.0.iter().any(|n| n == name) {
+            return Err(ManagerOrgExampleManagerError::Exists(format!(
+                "`{name}` exists"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl ManagerServer for Names {
+    fn create(&mut self, name: String) -> Result<u32, MethodErr> {
+        self.check(&name)?;
+        self.0.push(name);
+        Ok(self.0.len().try_into().unwrap())
+    }
+}
+
+/// Records the replies of `Crossroads`.
+#[derive(Default)]
+struct Replies(RefCell<Vec<Message>>);
+
+impl Sender for Replies {
+    fn send(&self, message: Message) -> Result<u32, ()> {
+        self.0.borrow_mut().push(message);
+        Ok(0)
+    }
+}
+
+fn create(name: &str) -> Message {
+    let mut call = Message::new_method_call(
+        "org.example.Manager",
+        "/org/example/Manager",
+        "org.example.Manager",
+        "Create",
+    )
+    .unwrap()
+    .append1(name);
+    call.set_serial(1);
+    call
+}
+
+#[test]
+fn declared_errors() {
+    let mut crossroads = Crossroads::new();
+    // Objects using any connection describe the same interfaces.
+    server::insert::<Manager<FakeBus>, _>(
+        &mut crossroads,
+        "/org/example/Manager",
+        Names::default(),
+    );
+    let replies = Replies::default();
+    crossroads.handle_message(create("a"), &replies).unwrap();
+    crossroads.handle_message(create("a"), &replies).unwrap();
+
+    let mut replies = replies.0.into_inner();
+    assert_eq!(replies[0].read1::<u32>().unwrap(), 1);
+    let error = replies[1].as_result().unwrap_err();
+    assert_eq!(error.name(), Some("org.example.Manager.Exists"));
+    assert_eq!(error.message(), Some("`a` exists"));
+}
+
+#[test]
+fn connections() {
+    // Only type checked, serving requires a bus.
+    let _serve = |sync: &Arc<SyncConnection>, local: &LocalConnection| {
+        server::register::<Manager, _>(sync, "/org/example/Manager", Names::default());
+        server::register::<Manager, _>(&**sync, "/org/example/Manager", Names::default());
+        server::register::<Manager, _>(local, "/org/example/Manager", Names::default());
+    };
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::os::fd::{FromRawFd, IntoRawFd};
+
+use dbus::Path;
+use dbus::arg::{OwnedFd, Variant};
+use dbus_client::verify::Declaration;
+use dbus_client::{DbusObject, dbus_object};
+
+mod common;
+use common::{FakeBus, member};
+
+dbus_object! {
+    Types("org.example.Types", "/org/example/Types")
+    "org.example.Types" {
+        Pair() -> (s u);
+        Struct() -> ((s u));
+        Owner() -> (s @Child);
+        Point: (i i);
+        ByName() -> a{s @Child};
+        ByChild() -> a{@Child u};
+        Wide(signed: x) -> t;
+        File() -> h;
+        Signatures(args: "a{sv}") -> "(su)";
+    }
+}
+
+dbus_object!(Child);
+
+fn child(name: &str) -> Path<'static> {
+    format!("/org/example/Child/{name}").into()
+}
+
+fn types() -> FakeBus {
+    FakeBus::new(|call| {
+        let reply = call.method_return();
+        Ok(match member(call).as_str() {
+            "Pair" => reply.append2("pair", 1u32),
+            "Struct" => reply.append1(("struct", 2u32)),
+            "Owner" => reply.append2("owner", child("owner")),
+            "Get" => reply.append1(Variant((3, 4))),
+            "ByName" => reply.append1(HashMap::from([("a", child("a")), ("b", child("b"))])),
+            "ByChild" => reply.append1(HashMap::from([(child("a"), 1u32)])),
+            "Wide" => reply.append1(call.read1::<i64>().unwrap().unsigned_abs()),
+            "File" => {
+                let file = File::open("Cargo.toml").unwrap();
+                // SAFETY: `file` gives up ownership of its descriptor.
+                reply.append1(unsafe { OwnedFd::from_raw_fd(file.into_raw_fd()) })
+            }
+            "Signatures" => reply.append1(("signature", 5u32)),
+            member => panic!("unexpected call of `{member}`"),
+        })
+    })
+}
+
+#[test]
+fn structs() {
+    let bus = types();
+    let types = Types::connect(&bus, None);
+    assert_eq!(types.pair().unwrap(), ("pair".to_owned(), 1));
+    assert_eq!(types.r#struct().unwrap(), (("struct".to_owned(), 2),));
+    assert_eq!(types.point().unwrap(), (3, 4));
+
+    let (name, child) = types.owner().unwrap();
+    assert_eq!(name, "owner");
+    assert_eq!(DbusObject::path(&child), &self::child("owner"));
+    assert_eq!(&**DbusObject::destination(&child), "org.example.Types");
+}
+
+#[test]
+// Objects are hashed by destination and path only.
+#[allow(clippy::mutable_key_type)]
+fn maps() {
+    let bus = types();
+    let types = Types::connect(&bus, None);
+
+    let children = types.by_name().unwrap();
+    assert_eq!(children.len(), 2);
+    for (name, child) in children {
+        assert_eq!(DbusObject::path(&child), &self::child(&name));
+    }
+
+    let children = types.by_child().unwrap();
+    let (child, value) = children.iter().next().unwrap();
+    assert_eq!(DbusObject::path(child), &self::child("a"));
+    assert_eq!(*value, 1);
+}
+
+#[test]
+fn basic_types() {
+    let bus = types();
+    let types = Types::connect(&bus, None);
+    assert_eq!(types.wide(-(1 << 40)).unwrap(), 1 << 40);
+
+    // SAFETY: `types.file()` gives up ownership of its descriptor.
+    let mut file = unsafe { File::from_raw_fd(types.file().unwrap().into_raw_fd()) };
+    let mut manifest = String::new();
+    file.read_to_string(&mut manifest).unwrap();
+    assert!(manifest.contains("dbus-client"));
+}
+
+#[test]
+fn signatures() {
+    let bus = types();
+    let types = Types::connect(&bus, None);
+    assert_eq!(
+        types.signatures(HashMap::new()).unwrap(),
+        (("signature".to_owned(), 5),)
+    );
+
+    let declaration = <Types>::declarations()
+        .into_iter()
+        .find(|d| {
+            matches!(d, Declaration::Method {
+                name: "Signatures",
+                ..
+            })
+        })
+        .unwrap();
+    let Declaration::Method { args, output, .. } = declaration else {
+        unreachable!()
+    };
+    assert_eq!(args.as_deref(), Some("a{sv}"));
+    assert_eq!(output.as_deref(), Some("(su)"));
+}
+
+/// As generated by `dbus-client-codegen` for `(ua{sv}(os))`.
+#[derive(Debug, dbus_client::Append, dbus_client::Arg, dbus_client::Get)]
+pub struct EntryField2(
+    pub dbus_client::dbus_type!(o),
+    pub dbus_client::dbus_type!(s),
+);
+
+#[derive(Debug, dbus_client::Append, dbus_client::Arg, dbus_client::Get)]
+pub struct Entry(
+    pub dbus_client::dbus_type!(u),
+    pub dbus_client::dbus_type!(a{s v}),
+    pub dbus_client::dbus_type!(EntryField2),
+);
+
+#[test]
+fn generated_structs() {
+    assert_eq!(
+        <Entry as dbus::arg::Arg>::signature().to_string(),
+        "(ua{sv}(os))"
+    );
+
+    let entry = Entry(1, HashMap::new(), EntryField2(child("a"), "a".into()));
+    let message = dbus::Message::new_signal("/org/example", "org.example.Types", "Entry")
+        .unwrap()
+        .append1(entry);
+    let Entry(id, properties, EntryField2(path, name)) = message.read1().unwrap();
+    assert_eq!(
+        (id, properties.len(), path, name.as_str()),
+        (1, 0, child("a"), "a")
+    );
+}